@@ -6,10 +6,10 @@
 
 //! An OSC address, e.g. `/lighting/right/...`
 
-use crate::{Batch, Batched, InvalidContents};
+use crate::{batch::padded_len, Batch, Batched, InvalidContents};
 
 #[cfg(feature = "alloc")]
-use crate::{Aligned4B, Decode, Misaligned4B};
+use crate::{Aligned4B, Decode, DecodeConfig, Misaligned4B};
 
 /// Error in an OSC address.
 #[non_exhaustive]
@@ -22,6 +22,8 @@ pub enum AddressErr {
     InvalidCharacter(u8),
     /// Invalid string contents for conversion to OSC.
     StringErr(InvalidContents),
+    /// Parsed string didn't start with `/`.
+    MissingLeadingSlash,
 }
 
 /// Convert from this type into an iterator in a specified way.
@@ -49,6 +51,16 @@ impl<'s> IntoIntoAddress for &'_ &'s str {
     }
 }
 
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl<'s> IntoIntoAddress for &'s alloc::string::String {
+    type IntoAddr = core::str::Bytes<'s>;
+    #[inline(always)]
+    fn into_into_addr(self) -> Self::IntoAddr {
+        self.bytes()
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[allow(unused_qualifications)]
 impl IntoIntoAddress for alloc::string::String {
@@ -59,14 +71,36 @@ impl IntoIntoAddress for alloc::string::String {
     }
 }
 
+/// How permissive to be about which bytes may appear in an address.
+#[non_exhaustive]
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum AddressCharPolicy {
+    /// Printable ASCII, minus characters blacklisted in the OSC spec.
+    #[default]
+    Strict,
+    /// Any non-null ASCII byte an OSC-string can hold, minus characters blacklisted in the OSC spec.
+    SpecOscString,
+    /// Like `Strict`, but also any non-ASCII byte, so UTF-8 multi-byte sequences pass through.
+    AllowUtf8Continuation,
+}
+
 /// Is this a valid ASCII character that's not blacklisted in the OSC spec?
 #[inline]
 #[must_use]
 pub const fn valid_address_character(c: u8) -> bool {
+    valid_address_character_with(c, AddressCharPolicy::Strict)
+}
+
+/// Is this character valid in an address under this policy?
+#[inline]
+#[must_use]
+pub const fn valid_address_character_with(c: u8, policy: AddressCharPolicy) -> bool {
     match c {
-        b' ' | b'#' | b'*' | b',' | b'/' | b'?' | b'[' | b']' | b'{' | b'}' => false,
+        0 | b' ' | b'#' | b'*' | b',' | b'/' | b'?' | b'[' | b']' | b'{' | b'}' => false,
         32..=126 => true, // "printable" ASCII characters, per the spec
-        _ => false,
+        1..=127 => matches!(policy, AddressCharPolicy::SpecOscString),
+        128..=255 => matches!(policy, AddressCharPolicy::AllowUtf8Continuation),
     }
 }
 
@@ -81,46 +115,54 @@ pub trait IntoAddress<Method: IntoIntoAddress>:
     #[inline(always)]
     #[allow(clippy::type_complexity)]
     fn into_address(self, method: Method) -> Result<Address<Self, Method>, AddressErr> {
-        #[allow(clippy::as_conversions, clippy::as_underscore, trivial_casts)]
-        let iter = self
-            .clone()
-            .into_iter()
-            .map(IntoIntoAddress::into_into_addr);
-        for mut s in iter {
-            match s.next() {
-                None => return Err(AddressErr::Empty),
-                Some(c) => {
-                    if !valid_address_character(c) {
-                        return Err(AddressErr::InvalidCharacter(c));
-                    }
-                }
-            }
-            for c in s {
-                if !valid_address_character(c) {
-                    return Err(AddressErr::InvalidCharacter(c));
-                }
-            }
-        }
-        let mut m = method.clone().into_into_addr();
-        match m.next() {
-            None => return Err(AddressErr::Empty),
-            Some(c) => {
-                if !valid_address_character(c) {
-                    return Err(AddressErr::InvalidCharacter(c));
-                }
-            }
-        }
-        for c in m {
-            if !valid_address_character(c) {
-                return Err(AddressErr::InvalidCharacter(c));
-            }
-        }
-        Ok(Address(self, method))
+        self.into_address_with(method, AddressCharPolicy::Strict)
+    }
+    /// Like `into_address`, but accepting characters under this policy, e.g. UTF-8 from a nonconforming sender.
+    /// # Errors
+    /// If the address is invalid under this policy.
+    #[inline(always)]
+    #[allow(clippy::type_complexity)]
+    fn into_address_with(
+        self,
+        method: Method,
+        policy: AddressCharPolicy,
+    ) -> Result<Address<Self, Method>, AddressErr> {
+        let address = Address(self, method);
+        address.validate_with(policy)?;
+        Ok(address)
     }
 }
 
 impl<I: Clone + IntoIterator> IntoAddress<I::Item> for I where I::Item: IntoIntoAddress {}
 
+/// Borrowed path segment or method, so both can share a type in `Address::by_ref`.
+#[derive(Clone, Debug)]
+pub(crate) enum SegmentRef<Segment, Method> {
+    /// Segment of the borrowed path.
+    Path(Segment),
+    /// Borrowed method.
+    Method(Method),
+}
+
+impl<Segment: IntoIntoAddress, Method: IntoIntoAddress<IntoAddr = Segment::IntoAddr>>
+    IntoIntoAddress for SegmentRef<Segment, Method>
+{
+    type IntoAddr = Segment::IntoAddr;
+    #[inline(always)]
+    fn into_into_addr(self) -> Self::IntoAddr {
+        match self {
+            Self::Path(segment) => segment.into_into_addr(),
+            Self::Method(method) => method.into_into_addr(),
+        }
+    }
+}
+
+/// Path of an address borrowed by `Address::by_ref`.
+pub(crate) type PathRef<'a, Path, Segment, Method> = core::iter::Map<
+    <&'a Path as IntoIterator>::IntoIter,
+    fn(Segment) -> SegmentRef<Segment, &'a Method>,
+>;
+
 /// An OSC address, e.g. `/lighting/right/...`
 #[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Address<Path: IntoIterator<Item = Method>, Method: IntoIntoAddress>(
@@ -128,6 +170,209 @@ pub struct Address<Path: IntoIterator<Item = Method>, Method: IntoIntoAddress>(
     pub(crate) Method,
 );
 
+impl<Path: IntoAddress<Method>, Method: IntoIntoAddress> Address<Path, Method> {
+    /// Build an address without validating it, e.g. once as a constant to reuse for many messages.
+    ///
+    /// `.into_osc()` re-validates every character on every call,
+    /// whereas `Message::new(address.clone(), ...)` does not.
+    ///
+    /// Invariant: every path segment and the method must be non-empty,
+    /// and every character must satisfy `valid_address_character` (or `valid_address_character_with` your policy).
+    /// Breaking this won't cause undefined behavior, but it will produce invalid OSC.
+    /// If you're not sure, call `validate` once after construction.
+    #[inline(always)]
+    pub const fn from_validated(path: Path, method: Method) -> Self {
+        Self(path, method)
+    }
+    /// Check that this address is valid (according to the OSC spec).
+    /// Only necessary after `from_validated`, since `into_address` already checks.
+    /// # Errors
+    /// If the address is invalid (according to the OSC spec).
+    #[inline]
+    pub fn validate(&self) -> Result<(), AddressErr> {
+        self.validate_with(AddressCharPolicy::Strict)
+    }
+    /// Like `validate`, but accepting characters under this policy.
+    /// # Errors
+    /// If the address is invalid under this policy.
+    #[inline]
+    pub fn validate_with(&self, policy: AddressCharPolicy) -> Result<(), AddressErr> {
+        // Path segments and the method follow exactly the same rules, checked in order.
+        self.0
+            .clone()
+            .into_iter()
+            .chain(core::iter::once(self.1.clone()))
+            .try_for_each(|segment| validate_segment(segment, policy))
+    }
+}
+
+impl<Method: IntoIntoAddress> Address<[Method; 0], Method> {
+    /// Single-segment address like `/ping`, with nothing before the method.
+    /// # Errors
+    /// If the method is invalid (according to the OSC spec).
+    #[inline]
+    pub fn root(method: Method) -> Result<Self, AddressErr> {
+        [].into_address(method)
+    }
+}
+
+/// Check a single path segment or method: non-empty, and every character valid.
+#[inline]
+fn validate_segment<Segment: IntoIntoAddress>(
+    segment: Segment,
+    policy: AddressCharPolicy,
+) -> Result<(), AddressErr> {
+    let mut empty = true;
+    for c in segment.into_into_addr() {
+        if !valid_address_character_with(c, policy) {
+            return Err(AddressErr::InvalidCharacter(c));
+        }
+        empty = false;
+    }
+    if empty {
+        Err(AddressErr::Empty)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl Address<alloc::vec::Vec<alloc::string::String>, alloc::string::String> {
+    /// Parse an address written the usual way, e.g. `/synth/1/gain`.
+    /// # Errors
+    /// If the address doesn't start with `/`, has an empty segment (e.g. `//` or a trailing `/`),
+    /// or contains an invalid character.
+    #[inline]
+    pub fn parse(s: &str) -> Result<Self, AddressErr> {
+        Self::parse_with(s, AddressCharPolicy::Strict)
+    }
+    /// Like `parse`, but accepting characters under this policy, e.g. `/caf\u{e9}` with `AllowUtf8Continuation`.
+    /// # Errors
+    /// If the address doesn't start with `/`, has an empty segment, or contains a character invalid under this policy.
+    #[inline]
+    pub fn parse_with(s: &str, policy: AddressCharPolicy) -> Result<Self, AddressErr> {
+        let path = s.strip_prefix('/').ok_or(AddressErr::MissingLeadingSlash)?;
+        let mut segments: alloc::vec::Vec<_> = path
+            .split('/')
+            .map(alloc::borrow::ToOwned::to_owned)
+            .collect();
+        // `split` always yields at least one (possibly empty) segment.
+        let method = segments.pop().unwrap_or_default();
+        let address = Self(segments, method);
+        address.validate_with(policy)?;
+        Ok(address)
+    }
+    /// Path segments before the method, e.g. `["lighting", "right"]` in `/lighting/right/brightness`.
+    #[inline(always)]
+    #[must_use]
+    pub fn segments(&self) -> &[alloc::string::String] {
+        &self.0
+    }
+    /// Final segment, e.g. `brightness` in `/lighting/right/brightness`.
+    #[inline(always)]
+    #[must_use]
+    pub fn method(&self) -> &str {
+        &self.1
+    }
+    /// Decode without checking that the padding after the null terminator is also null.
+    ///
+    /// See `DynamicString::decode_lenient` for the tradeoff.
+    /// # Errors
+    /// If the address is invalid or the iterator ends early.
+    #[inline]
+    pub fn decode_lenient<I: Iterator<Item = u8>>(
+        iter: &mut I,
+    ) -> Result<Self, Misaligned4B<AddressDecodeErr>> {
+        decode_address(iter, false, AddressCharPolicy::Strict)
+    }
+    /// Decode, accepting characters under `config.address_chars`.
+    /// # Errors
+    /// If the address is invalid under that policy or the iterator ends early.
+    #[inline]
+    pub fn decode_with_config<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+    ) -> Result<Self, Misaligned4B<AddressDecodeErr>> {
+        decode_address(iter, true, config.address_chars)
+    }
+}
+
+impl<Path: IntoIterator<Item = Method>, Method: IntoIntoAddress> Address<Path, Method> {
+    /// Borrow each segment, so the result encodes without consuming (or cloning) this address.
+    ///
+    /// Works whenever a borrowed path yields segments that encode like a borrowed method,
+    /// e.g. `&Vec<String>` and `&String`, or `&Segments` and `&&str`.
+    #[inline]
+    #[allow(clippy::as_conversions, clippy::as_underscore, trivial_casts)]
+    pub(crate) fn by_ref<'a, Segment: IntoIntoAddress>(
+        &'a self,
+    ) -> Address<PathRef<'a, Path, Segment, Method>, SegmentRef<Segment, &'a Method>>
+    where
+        &'a Path: IntoIterator<Item = Segment>,
+        &'a Method: IntoIntoAddress<IntoAddr = Segment::IntoAddr>,
+    {
+        Address(
+            (&self.0).into_iter().map(SegmentRef::Path as _),
+            SegmentRef::Method(&self.1),
+        )
+    }
+}
+
+impl<Path: IntoIterator<Item = Method> + Clone, Method: IntoIntoAddress> Address<Path, Method> {
+    /// Exact number of bytes this address will encode to, including its null terminator and padding.
+    #[inline]
+    #[must_use]
+    pub fn encoded_len(&self) -> usize {
+        let chars = self
+            .0
+            .clone()
+            .into_iter()
+            .chain(core::iter::once(self.1.clone()))
+            // Plus one for each leading slash.
+            .map(|segment| segment.into_into_addr().count().saturating_add(1))
+            .fold(0, usize::saturating_add);
+        // Plus one for the null terminator.
+        padded_len(chars.saturating_add(1))
+    }
+    /// Every segment including the method, e.g. `["a", "b", "c"]` for `/a/b/c`,
+    /// to compare addresses (or key routing tables) regardless of how each was built.
+    ///
+    /// Each byte becomes one character, which round-trips exactly for any valid (ASCII) address.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    #[allow(unused_qualifications)]
+    pub fn normalized(&self) -> alloc::vec::Vec<alloc::string::String> {
+        self.0
+            .clone()
+            .into_iter()
+            .chain(core::iter::once(self.1.clone()))
+            .map(|segment| segment.into_into_addr().map(char::from).collect())
+            .collect()
+    }
+}
+
+impl<Path: IntoIterator<Item = Method> + Clone, Method: IntoIntoAddress> core::fmt::Display
+    for Address<Path, Method>
+{
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for segment in self
+            .0
+            .clone()
+            .into_iter()
+            .chain(core::iter::once(self.1.clone()))
+        {
+            write!(f, "/")?;
+            for c in segment.into_into_addr() {
+                write!(f, "{}", char::from(c))?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl<Path: IntoIterator<Item = Method>, Method: IntoIntoAddress> IntoIterator
     for Address<Path, Method>
 {
@@ -157,7 +402,7 @@ impl<Path: IntoIterator<Item = Method>, Method: IntoIntoAddress> IntoIterator
 }
 
 /// Iterator over an OSC address, e.g. `/lighting/right/...`
-#[allow(missing_debug_implementations)]
+#[allow(clippy::struct_field_names, missing_debug_implementations)]
 pub struct Iter<I: Iterator>
 where
     I::Item: IntoIterator<Item = u8>,
@@ -196,8 +441,21 @@ where
             };
         }
     }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if !self.slash && self.bytes.is_none() {
+            // Already yielded the null terminator.
+            (0, Some(0))
+        } else {
+            // At least the null terminator is still to come.
+            (1, None)
+        }
+    }
 }
 
+/// Once the null terminator is out, there are no segment bytes left to read.
+impl<I: Iterator> core::iter::FusedIterator for Iter<I> where I::Item: IntoIterator<Item = u8> {}
+
 #[cfg(feature = "quickcheck")]
 #[allow(clippy::unwrap_used, unused_qualifications)]
 impl quickcheck::Arbitrary
@@ -207,12 +465,12 @@ impl quickcheck::Arbitrary
     fn arbitrary(g: &mut quickcheck::Gen) -> Self {
         let mut vv: alloc::vec::Vec<alloc::string::String> = alloc::vec::Vec::arbitrary(g);
         for v in &mut vv {
-            v.retain(|c| u8::try_from(c).map_or(false, valid_address_character));
+            v.retain(|c| u8::try_from(c).is_ok_and(valid_address_character));
         }
         vv.retain(|v| !v.is_empty());
         loop {
             let mut s = alloc::string::String::arbitrary(g);
-            s.retain(|c| u8::try_from(c).map_or(false, valid_address_character));
+            s.retain(|c| u8::try_from(c).is_ok_and(valid_address_character));
             if s.is_empty() {
                 continue;
             }
@@ -229,9 +487,31 @@ impl quickcheck::Arbitrary
     }
 }
 
+#[cfg(feature = "arbitrary")]
+#[allow(unused_qualifications)]
+impl<'a> arbitrary::Arbitrary<'a>
+    for Address<alloc::vec::Vec<alloc::string::String>, alloc::string::String>
+{
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut vv: alloc::vec::Vec<alloc::string::String> = u.arbitrary()?;
+        for v in &mut vv {
+            v.retain(|c| u8::try_from(c).is_ok_and(valid_address_character));
+        }
+        vv.retain(|v| !v.is_empty());
+        let mut s: alloc::string::String = u.arbitrary()?;
+        s.retain(|c| u8::try_from(c).is_ok_and(valid_address_character));
+        // Unlike QuickCheck, we can't just retry: the input might have run out.
+        if s.is_empty() {
+            s.push('x');
+        }
+        vv.into_address(s)
+            .map_err(|_err| arbitrary::Error::IncorrectFormat)
+    }
+}
+
 /// Error encountered while decoding an OSC address.
 #[non_exhaustive]
-#[cfg(feature = "alloc")]
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum AddressDecodeErr {
@@ -246,13 +526,14 @@ pub enum AddressDecodeErr {
     EmptySegment,
     /// Blacklisted character, e.g. `*`.
     PatternsNotYetImplemented(u8),
-    /// Not a printable ASCII character.
+    /// Not a printable ASCII character (or otherwise not allowed by the address character policy).
     NotPrintableAscii(u8),
     /// Returned a null terminator then the rest of the 4-byte chunk was not null.
     NullThenNonNull,
+    /// Non-ASCII bytes allowed by the address character policy, but not valid UTF-8.
+    InvalidUtf8,
 }
 
-#[cfg(feature = "alloc")]
 impl core::fmt::Display for AddressDecodeErr {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -286,8 +567,62 @@ impl core::fmt::Display for AddressDecodeErr {
                 "OSC address returned a null terminator, \
                 but then the rest of its 4-byte chunk was non-null."
             ),
+            &Self::InvalidUtf8 => write!(f, "OSC address with non-ASCII bytes that aren't UTF-8."),
+        }
+    }
+}
+
+/// Check one character after the leading slash, tracking whether the last one was a slash.
+///
+/// Every address decoder goes through this (and `check_address_end`), so they all agree on what's valid.
+#[inline]
+const fn check_address_char(
+    c: u8,
+    post_slash: &mut bool,
+    policy: AddressCharPolicy,
+) -> Result<(), AddressDecodeErr> {
+    match c {
+        b'/' => {
+            if *post_slash {
+                return Err(AddressDecodeErr::EmptySegment);
+            }
+            *post_slash = true;
+        }
+        b' ' | b'#' | b'*' | b',' | b'?' | b'[' | b']' | b'{' | b'}' => {
+            return Err(AddressDecodeErr::PatternsNotYetImplemented(c))
+        }
+        _ if !valid_address_character_with(c, policy) => {
+            return Err(AddressDecodeErr::NotPrintableAscii(c))
         }
+        _ => *post_slash = false,
+    }
+    Ok(())
+}
+
+/// Check that an address didn't end right after a slash (including the leading one).
+#[inline]
+const fn check_address_end(post_slash: bool) -> Result<(), AddressDecodeErr> {
+    if post_slash {
+        return Err(AddressDecodeErr::NoMethod);
+    }
+    Ok(())
+}
+
+/// Split a borrowed address like `/a/b/c` into its path (`a/b`) and method (`c`), checking every character.
+#[inline]
+pub(crate) fn split_address(s: &str) -> Result<(&str, &str), AddressDecodeErr> {
+    let Some(rest) = s.strip_prefix('/') else {
+        return Err(AddressDecodeErr::LeadingSlash {
+            actual: s.bytes().next().unwrap_or(b'\0'),
+        });
+    };
+    let mut post_slash = true;
+    for byte in rest.bytes() {
+        // Borrowed strings are checked to be ASCII before they get here.
+        check_address_char(byte, &mut post_slash, AddressCharPolicy::Strict)?;
     }
+    check_address_end(post_slash)?;
+    Ok(rest.rsplit_once('/').unwrap_or(("", rest)))
 }
 
 /// Parse an individual character with some mutable state passed in.
@@ -297,46 +632,33 @@ impl core::fmt::Display for AddressDecodeErr {
 fn parse_address_char<I: Iterator<Item = u8>>(
     byte: u8,
     bytes: &mut I,
+    strict: bool,
+    policy: AddressCharPolicy,
     post_slash: &mut bool,
-    v: &mut alloc::vec::Vec<alloc::string::String>,
-) -> Option<Result<alloc::string::String, Misaligned4B<AddressDecodeErr>>> {
-    match byte {
-        b'\0' => {
-            if *post_slash {
-                Some(Err(Misaligned4B::Other(AddressDecodeErr::NoMethod)))
-            } else {
-                for c in bytes {
-                    if c != b'\0' {
-                        return Some(Err(Misaligned4B::Other(AddressDecodeErr::NullThenNonNull)));
-                    }
-                }
-                // SAFETY:
-                // Control flow guarantees this will not be empty.
-                Some(Ok(unsafe { v.pop().unwrap_unchecked() }))
-            }
+    v: &mut alloc::vec::Vec<alloc::vec::Vec<u8>>,
+) -> Option<Result<alloc::vec::Vec<u8>, Misaligned4B<AddressDecodeErr>>> {
+    if byte == b'\0' {
+        if let Err(e) = check_address_end(*post_slash) {
+            return Some(Err(Misaligned4B::Other(e)));
         }
-        b'/' => {
-            if *post_slash {
-                Some(Err(Misaligned4B::Other(AddressDecodeErr::EmptySegment)))
-            } else {
-                v.push(alloc::string::String::new());
-                None
-            }
-        }
-        c @ (b' ' | b'#' | b'*' | b',' | b'?' | b'[' | b']' | b'{' | b'}') => Some(Err(
-            Misaligned4B::Other(AddressDecodeErr::PatternsNotYetImplemented(c)),
-        )),
-        c @ (..=31 | 127..) => Some(Err(Misaligned4B::Other(
-            AddressDecodeErr::NotPrintableAscii(c),
-        ))),
-        c => {
-            *post_slash = false;
-            // SAFETY:
-            // Control flow guarantees this will not be empty.
-            unsafe { v.last_mut().unwrap_unchecked() }.push(char::from(c));
-            None
+        if strict && bytes.any(|c| c != b'\0') {
+            return Some(Err(Misaligned4B::Other(AddressDecodeErr::NullThenNonNull)));
         }
+        // SAFETY:
+        // Control flow guarantees this will not be empty.
+        return Some(Ok(unsafe { v.pop().unwrap_unchecked() }));
+    }
+    if let Err(e) = check_address_char(byte, post_slash, policy) {
+        return Some(Err(Misaligned4B::Other(e)));
+    }
+    if byte == b'/' {
+        v.push(alloc::vec::Vec::new());
+    } else {
+        // SAFETY:
+        // Control flow guarantees this will not be empty.
+        unsafe { v.last_mut().unwrap_unchecked() }.push(byte);
     }
+    None
 }
 
 /// Parse four individual characters with some mutable state passed in.
@@ -345,60 +667,101 @@ fn parse_address_char<I: Iterator<Item = u8>>(
 #[allow(unsafe_code, unused_qualifications)]
 fn parse_address_chars<I: IntoIterator<Item = u8>>(
     bytes: I,
+    strict: bool,
+    policy: AddressCharPolicy,
     post_slash: &mut bool,
-    v: &mut alloc::vec::Vec<alloc::string::String>,
-) -> Option<Result<alloc::string::String, Misaligned4B<AddressDecodeErr>>> {
+    v: &mut alloc::vec::Vec<alloc::vec::Vec<u8>>,
+) -> Option<Result<alloc::vec::Vec<u8>, Misaligned4B<AddressDecodeErr>>> {
     let mut iter = bytes.into_iter();
     while let Some(byte) = iter.next() {
-        if let some @ Some(_) = parse_address_char(byte, &mut iter, post_slash, v) {
+        if let some @ Some(_) = parse_address_char(byte, &mut iter, strict, policy, post_slash, v) {
             return some;
         }
     }
     None
 }
 
+/// Turn each decoded segment into a string, which only fails if the policy let through bytes that aren't UTF-8.
+#[inline]
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+fn finish_address(
+    v: alloc::vec::Vec<alloc::vec::Vec<u8>>,
+    method: alloc::vec::Vec<u8>,
+) -> Result<
+    Address<alloc::vec::Vec<alloc::string::String>, alloc::string::String>,
+    Misaligned4B<AddressDecodeErr>,
+> {
+    let utf8 = |bytes| {
+        alloc::string::String::from_utf8(bytes)
+            .map_err(|_not_utf8| Misaligned4B::Other(AddressDecodeErr::InvalidUtf8))
+    };
+    Ok(Address(
+        v.into_iter().map(utf8).collect::<Result<_, _>>()?,
+        utf8(method)?,
+    ))
+}
+
 #[cfg(feature = "alloc")]
 #[allow(unused_qualifications)]
 impl Decode for Address<alloc::vec::Vec<alloc::string::String>, alloc::string::String> {
     type Error = AddressDecodeErr;
     #[inline]
-    #[allow(unsafe_code)]
     fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
-        let mut first = Aligned4B::decode(iter)?.into_iter();
+        decode_address(iter, true, AddressCharPolicy::Strict)
+    }
+}
+
+/// Decode an OSC address, optionally checking that its padding is null.
+#[inline]
+#[cfg(feature = "alloc")]
+#[allow(unsafe_code, unused_qualifications)]
+fn decode_address<I: Iterator<Item = u8>>(
+    iter: &mut I,
+    strict: bool,
+    policy: AddressCharPolicy,
+) -> Result<
+    Address<alloc::vec::Vec<alloc::string::String>, alloc::string::String>,
+    Misaligned4B<AddressDecodeErr>,
+> {
+    let mut first = Aligned4B::decode(iter)?.into_iter();
+    // SAFETY:
+    // Control flow guarantees this will not be empty.
+    let actual = unsafe { first.next().unwrap_unchecked() };
+    if actual != b'/' {
+        return Err(Misaligned4B::Other(AddressDecodeErr::LeadingSlash {
+            actual,
+        }));
+    }
+    let mut post_slash = true;
+    // The last segment is always the one being read, so whichever is unfinished at the null
+    // terminator becomes the method: `/ping` leaves no path segments at all.
+    let mut v = alloc::vec![alloc::vec::Vec::new()];
+    match parse_address_char(
         // SAFETY:
         // Control flow guarantees this will not be empty.
-        let actual = unsafe { first.next().unwrap_unchecked() };
-        if actual != b'/' {
-            return Err(Misaligned4B::Other(AddressDecodeErr::LeadingSlash {
-                actual,
-            }));
-        }
-        let mut post_slash = true;
-        let mut v = alloc::vec![alloc::string::String::new()];
-        match parse_address_char(
-            // SAFETY:
-            // Control flow guarantees this will not be empty.
-            unsafe { first.next().unwrap_unchecked() },
-            &mut first,
-            &mut post_slash,
-            &mut v,
-        ) {
-            None => {}
-            Some(Ok(head)) => return Ok(Address(v, head)),
-            Some(Err(e)) => return Err(e),
-        };
-        match parse_address_chars(&mut first, &mut post_slash, &mut v) {
+        unsafe { first.next().unwrap_unchecked() },
+        &mut first,
+        strict,
+        policy,
+        &mut post_slash,
+        &mut v,
+    ) {
+        None => {}
+        Some(Ok(head)) => return finish_address(v, head),
+        Some(Err(e)) => return Err(e),
+    }
+    match parse_address_chars(&mut first, strict, policy, &mut post_slash, &mut v) {
+        None => {}
+        Some(Ok(head)) => return finish_address(v, head),
+        Some(Err(e)) => return Err(e),
+    }
+    loop {
+        let bytes = Aligned4B::decode(iter)?;
+        match parse_address_chars(bytes, strict, policy, &mut post_slash, &mut v) {
             None => {}
-            Some(Ok(head)) => return Ok(Address(v, head)),
+            Some(Ok(head)) => return finish_address(v, head),
             Some(Err(e)) => return Err(e),
         }
-        loop {
-            let bytes = Aligned4B::decode(iter)?;
-            match parse_address_chars(bytes, &mut post_slash, &mut v) {
-                None => {}
-                Some(Ok(head)) => return Ok(Address(v, head)),
-                Some(Err(e)) => return Err(e),
-            }
-        }
     }
 }
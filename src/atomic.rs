@@ -6,11 +6,14 @@
 
 //! Integer, float, string, or blob.
 
-use crate::{Aligned4B, Batch, Batched, Decode, IntoOsc, Misaligned4B, Tag};
+use crate::{
+    batch::{padded_len, padded_len_checked},
+    Aligned4B, Batch, Batched, Decode, DecodeConfig, Endian, IntoOsc, Misaligned4B, Tag,
+};
 use core::iter::{once, Chain, Copied, Once};
 
 #[cfg(feature = "alloc")]
-use crate::Data;
+use crate::{Data, DataIter, DataRefIter, NegativeBlobSize};
 
 //////////////// Trait definition
 
@@ -20,8 +23,10 @@ pub trait Atomic:
 where
     InvalidContents: From<<Self as TryFrom<Self::AsRust>>::Error>,
 {
-    /// OSC type tag: a single character denoting this type.
-    fn type_tag(&self) -> Tag;
+    /// OSC type tag: a single character denoting this type, e.g. `b'i'` for `Integer`.
+    ///
+    /// A byte rather than a `Tag`, since `Data::Unknown` carries tags this crate doesn't recognize.
+    fn type_tag(&self) -> u8;
     /// Rust representation of this OSC type (e.g. `Integer` -> `i32`).
     type AsRust: IntoAtomic<AsAtomic = Self, AsOsc = (Self,)>;
     /// Convert from OSC to a value Rust can work with.
@@ -38,6 +43,8 @@ where
     }
     /// Iterator over the OSC-formatted value.
     type Iter: Iterator<Item = u8>;
+    /// Exact number of bytes this value will encode to, including padding.
+    fn byte_len(&self) -> usize;
 }
 
 //////////////// Struct definitions
@@ -49,89 +56,140 @@ pub struct Integer([u8; 4]);
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Float([u8; 4]);
 /// Null-terminated (not your responsibility!) byte string.
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
 pub struct String<'s>(&'s str);
 /// Arbitrary known-length collection of bytes.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Blob<'b>(&'b [u8]);
+/// 64-bit big-endian fixed-point time in NTP format: seconds since 1900 and fractions of a second.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TimeTag {
+    /// Whole seconds since midnight on January 1, 1900.
+    pub(crate) seconds: u32,
+    /// Fractional part of a second, in units of 2^-32 seconds.
+    pub(crate) sub_second: u32,
+}
 
 /// Null-terminated (not your responsibility!) byte string.
 #[cfg(feature = "alloc")]
-#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct DynamicString(alloc::string::String);
+#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct DynamicString(pub(crate) alloc::string::String);
 /// Arbitrary known-length collection of bytes.
 #[allow(unused_qualifications)]
 #[cfg(feature = "alloc")]
 #[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct DynamicBlob(alloc::vec::Vec<u8>);
+pub struct DynamicBlob(pub(crate) alloc::vec::Vec<u8>);
 
 //////////////// Trait implementations
 
 impl Atomic for Integer {
     #[inline(always)]
-    fn type_tag(&self) -> Tag {
-        Tag::Integer
+    fn type_tag(&self) -> u8 {
+        Tag::Integer.as_byte()
     }
     type AsRust = i32;
     type Iter = core::array::IntoIter<u8, 4>;
+    #[inline(always)]
+    fn byte_len(&self) -> usize {
+        4
+    }
 }
 impl Atomic for Float {
     #[inline(always)]
-    fn type_tag(&self) -> Tag {
-        Tag::Float
+    fn type_tag(&self) -> u8 {
+        Tag::Float.as_byte()
     }
     type AsRust = f32;
     type Iter = core::array::IntoIter<u8, 4>;
+    #[inline(always)]
+    fn byte_len(&self) -> usize {
+        4
+    }
 }
 impl<'s> Atomic for String<'s> {
     #[inline(always)]
-    fn type_tag(&self) -> Tag {
-        Tag::String
+    fn type_tag(&self) -> u8 {
+        Tag::String.as_byte()
     }
     type AsRust = &'s str;
     type Iter = Chain<core::str::Bytes<'s>, Once<u8>>;
+    #[inline(always)]
+    fn byte_len(&self) -> usize {
+        // Plus one for the null terminator.
+        padded_len(self.0.len().saturating_add(1))
+    }
 }
 impl<'b> Atomic for Blob<'b> {
     #[inline(always)]
-    fn type_tag(&self) -> Tag {
-        Tag::Blob
+    fn type_tag(&self) -> u8 {
+        Tag::Blob.as_byte()
     }
     type AsRust = &'b [u8];
-    type Iter = Copied<core::slice::Iter<'b, u8>>;
+    type Iter = SizePrefixed<Copied<core::slice::Iter<'b, u8>>>;
+    #[inline(always)]
+    fn byte_len(&self) -> usize {
+        // Plus four for the size.
+        padded_len(self.0.len()).saturating_add(4)
+    }
 }
 
 #[cfg(feature = "alloc")]
 impl Atomic for Data {
     #[inline(always)]
-    fn type_tag(&self) -> Tag {
+    fn type_tag(&self) -> u8 {
         match self {
             &Data::Integer(ref i) => i.type_tag(),
             &Data::Float(ref f) => f.type_tag(),
             &Data::String(ref s) => s.type_tag(),
             &Data::Blob(ref b) => b.type_tag(),
+            #[cfg(feature = "nonstandard-types")]
+            &Data::Nil => Tag::Nil.as_byte(),
+            &Data::Unknown(ref u) => u.tag(),
         }
     }
     type AsRust = Data;
-    type Iter = alloc::vec::IntoIter<u8>;
+    type Iter = DataIter;
+    #[inline(always)]
+    fn byte_len(&self) -> usize {
+        match self {
+            &Data::Integer(ref i) => i.byte_len(),
+            &Data::Float(ref f) => f.byte_len(),
+            &Data::String(ref s) => s.byte_len(),
+            &Data::Blob(ref b) => b.byte_len(),
+            #[cfg(feature = "nonstandard-types")]
+            &Data::Nil => 0,
+            &Data::Unknown(ref u) => u.bytes().len(),
+        }
+    }
 }
 #[cfg(feature = "alloc")]
 impl Atomic for DynamicString {
     #[inline(always)]
-    fn type_tag(&self) -> Tag {
-        Tag::String
+    fn type_tag(&self) -> u8 {
+        Tag::String.as_byte()
     }
     type AsRust = alloc::string::String;
     type Iter = Chain<alloc::vec::IntoIter<u8>, Once<u8>>;
+    #[inline(always)]
+    fn byte_len(&self) -> usize {
+        // Plus one for the null terminator.
+        padded_len(self.0.len().saturating_add(1))
+    }
 }
 #[cfg(feature = "alloc")]
 impl Atomic for DynamicBlob {
     #[inline(always)]
-    fn type_tag(&self) -> Tag {
-        Tag::Blob
+    fn type_tag(&self) -> u8 {
+        Tag::Blob.as_byte()
     }
     #[allow(unused_qualifications)]
     type AsRust = alloc::vec::Vec<u8>;
-    type Iter = alloc::vec::IntoIter<u8>;
+    type Iter = SizePrefixed<alloc::vec::IntoIter<u8>>;
+    #[inline(always)]
+    fn byte_len(&self) -> usize {
+        // Plus four for the size.
+        padded_len(self.0.len()).saturating_add(4)
+    }
 }
 
 //////////////// `From` implementations
@@ -141,9 +199,14 @@ impl Atomic for DynamicBlob {
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum InvalidContents {
     /// Non-ASCII character in a Rust string.
-    NonAscii,
+    NonAscii {
+        /// Byte offset of the first non-ASCII byte.
+        index: usize,
+    },
     /// Null byte in an otherwise normal ASCII string.
     NullInString,
+    /// Blob or bundle element longer than `i32::MAX` bytes, so its size can't be encoded.
+    TooLarge,
 }
 
 impl From<core::convert::Infallible> for InvalidContents {
@@ -164,11 +227,45 @@ impl From<core::convert::Infallible> for InvalidContents {
     }
 }
 
-impl TryFrom<i32> for Integer {
-    type Error = core::convert::Infallible;
+impl Integer {
+    /// Convert without a `Result`, since every `i32` is a valid OSC integer.
+    ///
+    /// ```rust
+    /// use osc::Integer;
+    /// assert!(Integer::from_i32(-1).into_iter().eq([0xFF; 4]));
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_i32(value: i32) -> Self {
+        Self(value.to_be_bytes())
+    }
+    /// Wrap four big-endian bytes as-is, e.g. straight from another library's buffer.
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_be_bytes(bytes: [u8; 4]) -> Self {
+        Self(bytes)
+    }
+    /// The four big-endian bytes this encodes to, without converting to `i32` first.
+    #[inline(always)]
+    #[must_use]
+    pub const fn to_be_bytes(&self) -> [u8; 4] {
+        self.0
+    }
+    /// Decode in the byte order given by `config` (big-endian per the spec unless told otherwise).
+    /// # Errors
+    /// If the iterator ends early.
+    #[inline]
+    pub fn decode_with_config<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+    ) -> Result<Self, Misaligned4B<core::convert::Infallible>> {
+        decode_word(iter, config.endianness).map(Self)
+    }
+}
+impl From<i32> for Integer {
     #[inline(always)]
-    fn try_from(value: i32) -> Result<Self, Self::Error> {
-        Ok(Self(value.to_be_bytes()))
+    fn from(value: i32) -> Self {
+        Self::from_i32(value)
     }
 }
 impl From<Integer> for i32 {
@@ -178,11 +275,58 @@ impl From<Integer> for i32 {
     }
 }
 
-impl TryFrom<f32> for Float {
-    type Error = core::convert::Infallible;
+impl Float {
+    /// Convert without a `Result`, since every `f32` is a valid OSC float.
+    ///
+    /// ```rust
+    /// use osc::Float;
+    /// assert!(Float::from_f32(1.0).into_iter().eq([0x3F, 0x80, 0, 0]));
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_f32(value: f32) -> Self {
+        Self(value.to_be_bytes())
+    }
+    /// Wrap four big-endian bytes as-is, e.g. straight from another library's buffer.
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_be_bytes(bytes: [u8; 4]) -> Self {
+        Self(bytes)
+    }
+    /// The four big-endian bytes this encodes to, without converting to `f32` first.
+    #[inline(always)]
+    #[must_use]
+    pub const fn to_be_bytes(&self) -> [u8; 4] {
+        self.0
+    }
+    /// Decode in the byte order given by `config` (big-endian per the spec unless told otherwise).
+    /// # Errors
+    /// If the iterator ends early.
+    #[inline]
+    pub fn decode_with_config<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+    ) -> Result<Self, Misaligned4B<core::convert::Infallible>> {
+        decode_word(iter, config.endianness).map(Self)
+    }
+}
+
+/// Read four bytes in the given order, returning them big-endian.
+#[inline]
+fn decode_word<I: Iterator<Item = u8>>(
+    iter: &mut I,
+    endianness: Endian,
+) -> Result<[u8; 4], Misaligned4B<core::convert::Infallible>> {
+    let Aligned4B(a, b, c, d, _) = Aligned4B::decode(iter)?;
+    Ok(match endianness {
+        Endian::Big => [a, b, c, d],
+        Endian::Little => [d, c, b, a],
+    })
+}
+impl From<f32> for Float {
     #[inline(always)]
-    fn try_from(value: f32) -> Result<Self, Self::Error> {
-        Ok(Self(value.to_be_bytes()))
+    fn from(value: f32) -> Self {
+        Self::from_f32(value)
     }
 }
 impl From<Float> for f32 {
@@ -192,17 +336,44 @@ impl From<Float> for f32 {
     }
 }
 
+/// Check that a Rust string can be sent as an OSC string: ASCII all the way through, with no nulls.
+#[inline]
+fn check_contents(s: &str) -> Result<(), InvalidContents> {
+    match s.bytes().position(|b| !b.is_ascii()) {
+        Some(index) => Err(InvalidContents::NonAscii { index }),
+        None if s.contains('\0') => Err(InvalidContents::NullInString),
+        None => Ok(()),
+    }
+}
+
+/// Check that contents this long can be preceded by their size as a 32-bit integer, and return that size.
+#[inline]
+pub(crate) fn check_size_prefix(len: usize) -> Result<i32, InvalidContents> {
+    i32::try_from(len).map_err(|_overflow| InvalidContents::TooLarge)
+}
+
+impl<'s> String<'s> {
+    /// Split off the longest prefix that's a valid OSC string, e.g. to send what's usable of user input.
+    ///
+    /// The rest starts at the first non-ASCII or null character (or is empty if everything was valid).
+    #[inline]
+    #[must_use]
+    pub fn from_prefix(s: &'s str) -> (Self, &'s str) {
+        // Everything before the first non-ASCII byte is ASCII, so this is always a character boundary.
+        let (prefix, rest) = s.split_at(
+            s.bytes()
+                .position(|b| b == b'\0' || !b.is_ascii())
+                .unwrap_or(s.len()),
+        );
+        (Self(prefix), rest)
+    }
+}
+
 impl<'s> TryFrom<&'s str> for String<'s> {
     type Error = InvalidContents;
     #[inline(always)]
     fn try_from(value: &'s str) -> Result<Self, Self::Error> {
-        if !value.is_ascii() {
-            Err(InvalidContents::NonAscii)
-        } else if value.contains('\0') {
-            Err(InvalidContents::NullInString)
-        } else {
-            Ok(Self(value))
-        }
+        check_contents(value).map(|()| Self(value))
     }
 }
 impl<'s> From<String<'s>> for &'s str {
@@ -213,17 +384,17 @@ impl<'s> From<String<'s>> for &'s str {
 }
 
 impl<'b> TryFrom<&'b [u8]> for Blob<'b> {
-    type Error = core::convert::Infallible;
+    type Error = InvalidContents;
     #[inline(always)]
     fn try_from(value: &'b [u8]) -> Result<Self, Self::Error> {
-        Ok(Self(value))
+        check_size_prefix(value.len()).map(|_| Self(value))
     }
 }
 impl<'b, const N: usize> TryFrom<&'b [u8; N]> for Blob<'b> {
-    type Error = core::convert::Infallible;
+    type Error = InvalidContents;
     #[inline(always)]
     fn try_from(value: &'b [u8; N]) -> Result<Self, Self::Error> {
-        Ok(Self(value))
+        check_size_prefix(N).map(|_| Self(value))
     }
 }
 impl<'b> From<Blob<'b>> for &'b [u8] {
@@ -238,13 +409,7 @@ impl TryFrom<alloc::string::String> for DynamicString {
     type Error = InvalidContents;
     #[inline(always)]
     fn try_from(value: alloc::string::String) -> Result<Self, Self::Error> {
-        if !value.is_ascii() {
-            Err(InvalidContents::NonAscii)
-        } else if value.contains('\0') {
-            Err(InvalidContents::NullInString)
-        } else {
-            Ok(Self(value))
-        }
+        check_contents(&value).map(|()| Self(value))
     }
 }
 #[cfg(feature = "alloc")]
@@ -258,10 +423,10 @@ impl From<DynamicString> for alloc::string::String {
 #[cfg(feature = "alloc")]
 #[allow(unused_qualifications)]
 impl TryFrom<alloc::vec::Vec<u8>> for DynamicBlob {
-    type Error = core::convert::Infallible;
+    type Error = InvalidContents;
     #[inline(always)]
     fn try_from(value: alloc::vec::Vec<u8>) -> Result<Self, Self::Error> {
-        Ok(Self(value))
+        check_size_prefix(value.len()).map(|_| Self(value))
     }
 }
 #[cfg(feature = "alloc")]
@@ -273,8 +438,348 @@ impl From<DynamicBlob> for alloc::vec::Vec<u8> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl AsRef<str> for DynamicString {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+#[cfg(feature = "alloc")]
+impl AsRef<[u8]> for DynamicBlob {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+//////////////// Slicing blobs
+
+impl Blob<'_> {
+    /// Number of bytes, not counting the size or padding.
+    #[inline(always)]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Whether there are no bytes at all.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Bytes at this index or range, or `None` if out of bounds.
+    #[inline(always)]
+    #[must_use]
+    pub fn get<R: core::slice::SliceIndex<[u8]>>(&self, index: R) -> Option<&R::Output> {
+        self.0.get(index)
+    }
+}
+impl<R: core::slice::SliceIndex<[u8]>> core::ops::Index<R> for Blob<'_> {
+    type Output = R::Output;
+    /// Panics if out of bounds, just like slicing; see `get` to avoid that.
+    #[inline(always)]
+    #[allow(clippy::indexing_slicing)]
+    fn index(&self, index: R) -> &Self::Output {
+        &self.0[index]
+    }
+}
+#[cfg(feature = "alloc")]
+impl<R: core::slice::SliceIndex<[u8]>> core::ops::Index<R> for DynamicBlob {
+    type Output = R::Output;
+    /// Panics if out of bounds, just like slicing; see `get` to avoid that.
+    #[inline(always)]
+    #[allow(clippy::indexing_slicing)]
+    fn index(&self, index: R) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+//////////////// Writing blobs incrementally
+
+/// Build a blob a piece at a time, e.g. serializing a struct field by field, then `finish` it.
+///
+/// The size prefix and padding aren't stored: they're computed from the final length when encoding.
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BlobWriter(alloc::vec::Vec<u8>);
+
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl BlobWriter {
+    /// Nothing written yet.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(alloc::vec::Vec::new())
+    }
+    /// Append these bytes.
+    #[inline(always)]
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+    /// Number of bytes written so far, not counting the size or padding.
+    #[inline(always)]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Whether nothing has been written yet.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Everything written so far, as a blob.
+    /// # Errors
+    /// If more than `i32::MAX` bytes were written.
+    #[inline(always)]
+    pub fn finish(self) -> Result<DynamicBlob, InvalidContents> {
+        DynamicBlob::try_from(self.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Write for BlobWriter {
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+//////////////// Comparisons with Rust types
+
+/// Compare an owned OSC type directly with the borrowed Rust type it wraps, in both directions.
+#[cfg(feature = "alloc")]
+macro_rules! impl_eq {
+    ($osc:ident, $rust:ty) => {
+        impl PartialEq<$rust> for $osc {
+            #[inline(always)]
+            fn eq(&self, other: &$rust) -> bool {
+                *self.0 == *other
+            }
+        }
+        impl PartialEq<&$rust> for $osc {
+            #[inline(always)]
+            fn eq(&self, other: &&$rust) -> bool {
+                *self.0 == **other
+            }
+        }
+        impl PartialEq<$osc> for $rust {
+            #[inline(always)]
+            fn eq(&self, other: &$osc) -> bool {
+                *self == *other.0
+            }
+        }
+        impl PartialEq<$osc> for &$rust {
+            #[inline(always)]
+            fn eq(&self, other: &$osc) -> bool {
+                **self == *other.0
+            }
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl_eq!(DynamicString, str);
+
+/// Hashes exactly like the underlying `str`, so that it matches `DynamicString` with the same contents.
+impl core::hash::Hash for String<'_> {
+    #[inline(always)]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+/// Hashes exactly like the underlying `str`, so that it matches `String` with the same contents.
+#[cfg(feature = "alloc")]
+impl core::hash::Hash for DynamicString {
+    #[inline(always)]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+/// Look up `DynamicString` keys by `&str` (including `String` via `<&str>::from`).
+#[cfg(feature = "alloc")]
+impl core::borrow::Borrow<str> for DynamicString {
+    #[inline(always)]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+#[cfg(feature = "alloc")]
+impl PartialEq<String<'_>> for DynamicString {
+    #[inline(always)]
+    fn eq(&self, other: &String<'_>) -> bool {
+        self.as_str() == other.0
+    }
+}
+#[cfg(feature = "alloc")]
+impl PartialEq<DynamicString> for String<'_> {
+    #[inline(always)]
+    fn eq(&self, other: &DynamicString) -> bool {
+        self.0 == other.as_str()
+    }
+}
+#[cfg(feature = "alloc")]
+impl_eq!(DynamicBlob, [u8]);
+
+//////////////// Time tags
+
+/// Seconds between the NTP epoch (1900) and the Unix epoch (1970).
+const UNIX_EPOCH_AS_NTP: u64 = 2_208_988_800;
+
+/// Nanoseconds per second.
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// Truncate to the lowest 32 bits.
+#[inline(always)]
+const fn low_32_bits(x: u64) -> u32 {
+    let [_, _, _, _, low @ ..] = x.to_be_bytes();
+    u32::from_be_bytes(low)
+}
+
+impl TimeTag {
+    /// Time tag from whole seconds since 1900 and a fraction of a second in units of 2^-32 seconds.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(seconds: u32, sub_second: u32) -> Self {
+        Self {
+            seconds,
+            sub_second,
+        }
+    }
+    /// Special value meaning "as soon as possible" instead of any particular time.
+    #[inline(always)]
+    #[must_use]
+    pub const fn immediately() -> Self {
+        Self::new(0, 1)
+    }
+    /// Whole seconds since midnight on January 1, 1900.
+    #[inline(always)]
+    #[must_use]
+    pub const fn seconds(&self) -> u32 {
+        self.seconds
+    }
+    /// Fractional part of a second, in units of 2^-32 seconds.
+    #[inline(always)]
+    #[must_use]
+    pub const fn sub_second(&self) -> u32 {
+        self.sub_second
+    }
+    /// Time tag for this long after the Unix epoch (e.g. `SystemTime::now().duration_since(UNIX_EPOCH)`).
+    ///
+    /// Seconds wrap around every 2^32 (NTP eras), so only times before 2104 round-trip;
+    /// see `to_duration_since_epoch`.
+    #[inline]
+    #[must_use]
+    #[allow(
+        clippy::arithmetic_side_effects,
+        clippy::integer_division,
+        clippy::integer_division_remainder_used
+    )]
+    pub fn from_duration_since_epoch(since_epoch: core::time::Duration) -> Self {
+        let seconds = since_epoch.as_secs().wrapping_add(UNIX_EPOCH_AS_NTP);
+        // Fewer than 2^30 nanoseconds, so shifting by 32 can't overflow, and dividing leaves less than 2^32.
+        let fraction = (u64::from(since_epoch.subsec_nanos()) << 32_u32) / NANOS_PER_SECOND;
+        Self::new(low_32_bits(seconds), low_32_bits(fraction))
+    }
+    /// How long after the Unix epoch this time tag is, or `None` for `immediately()` or anything before 1970.
+    ///
+    /// Follows RFC 4330 for NTP era rollover: if the highest bit of `seconds` is set,
+    /// the time is between 1968 and 2036; otherwise, between 2036 and 2104.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn to_duration_since_epoch(&self) -> Option<core::time::Duration> {
+        if *self == Self::immediately() {
+            return None;
+        }
+        let era_start = if self.seconds >> 31_u32 == 0 {
+            1 << 32_u32
+        } else {
+            0
+        };
+        let since_epoch = (era_start + u64::from(self.seconds)).checked_sub(UNIX_EPOCH_AS_NTP)?;
+        // Less than 2^32 times less than 2^30, so no overflow; rounding up makes `from_duration_since_epoch` round-trip.
+        let nanos = (u64::from(self.sub_second) * NANOS_PER_SECOND).div_ceil(1 << 32_u32);
+        Some(core::time::Duration::from_secs(since_epoch) + core::time::Duration::from_nanos(nanos))
+    }
+}
+
+impl IntoIterator for TimeTag {
+    type IntoIter = core::array::IntoIter<u8, 8>;
+    type Item = u8;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let mut bytes = [0; 8];
+        let (seconds, sub_second) = bytes.split_at_mut(4);
+        seconds.copy_from_slice(&self.seconds.to_be_bytes());
+        sub_second.copy_from_slice(&self.sub_second.to_be_bytes());
+        bytes.into_iter()
+    }
+}
+
 //////////////// `IntoIterator` implementations
 
+/// Blob contents preceded by their size as a big-endian 32-bit integer.
+#[derive(Clone, Debug)]
+pub struct SizePrefixed<I: ExactSizeIterator<Item = u8>> {
+    /// Big-endian size, not yet yielded.
+    size: core::array::IntoIter<u8, 4>,
+    /// Blob contents.
+    contents: I,
+}
+
+impl<I: ExactSizeIterator<Item = u8>> SizePrefixed<I> {
+    /// Prefix these contents with their length.
+    ///
+    /// Only for blob contents, which are checked to fit in an `i32` when the blob is built.
+    #[inline]
+    pub(crate) fn new(contents: I) -> Self {
+        let size = i32::try_from(contents.len()).unwrap_or(i32::MAX);
+        Self {
+            size: size.to_be_bytes().into_iter(),
+            contents,
+        }
+    }
+}
+
+impl<I: ExactSizeIterator<Item = u8>> Iterator for SizePrefixed<I> {
+    type Item = u8;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.size.next().or_else(|| self.contents.next())
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<I: ExactSizeIterator<Item = u8>> ExactSizeIterator for SizePrefixed<I> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.size.len().saturating_add(self.contents.len())
+    }
+}
+
+impl<I: DoubleEndedIterator<Item = u8> + ExactSizeIterator> DoubleEndedIterator
+    for SizePrefixed<I>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.contents.next_back().or_else(|| self.size.next_back())
+    }
+}
+
+impl<I: ExactSizeIterator<Item = u8> + core::iter::FusedIterator> core::iter::FusedIterator
+    for SizePrefixed<I>
+{
+}
+
 impl IntoIterator for Integer {
     type IntoIter = Batched<<Self as Atomic>::Iter>;
     type Item = u8;
@@ -307,7 +812,7 @@ impl IntoIterator for Blob<'_> {
     type Item = u8;
     #[inline(always)]
     fn into_iter(self) -> Self::IntoIter {
-        self.0.iter().copied().batch()
+        SizePrefixed::new(self.0.iter().copied()).batch()
     }
 }
 
@@ -317,14 +822,36 @@ impl IntoIterator for Data {
     type Item = u8;
     #[inline(always)]
     fn into_iter(self) -> Self::IntoIter {
-        #[allow(unused_qualifications)]
-        let v: alloc::vec::Vec<_> = match self {
-            Data::Integer(i) => i.into_iter().collect(),
-            Data::Float(f) => f.into_iter().collect(),
-            Data::String(s) => s.into_iter().collect(),
-            Data::Blob(b) => b.into_iter().collect(),
-        };
-        v.into_iter().batch()
+        match self {
+            Data::Integer(i) => DataIter::Integer(i.into_iter().unbatch()),
+            Data::Float(f) => DataIter::Float(f.into_iter().unbatch()),
+            Data::String(s) => DataIter::String(s.into_iter().unbatch()),
+            Data::Blob(b) => DataIter::Blob(b.into_iter().unbatch()),
+            #[cfg(feature = "nonstandard-types")]
+            Data::Nil => DataIter::Nil,
+            Data::Unknown(u) => DataIter::Unknown(u.bytes.into_iter()),
+        }
+        .batch()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[allow(clippy::into_iter_without_iter)]
+impl<'d> IntoIterator for &'d Data {
+    type IntoIter = Batched<DataRefIter<'d>>;
+    type Item = u8;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            &Data::Integer(i) => DataRefIter::Integer(i.into_iter().unbatch()),
+            &Data::Float(f) => DataRefIter::Float(f.into_iter().unbatch()),
+            &Data::String(ref s) => DataRefIter::String(s.0.bytes().chain(once(0))),
+            &Data::Blob(ref b) => DataRefIter::Blob(SizePrefixed::new(b.0.iter().copied())),
+            #[cfg(feature = "nonstandard-types")]
+            &Data::Nil => DataRefIter::Nil,
+            &Data::Unknown(ref u) => DataRefIter::Unknown(u.bytes().iter().copied()),
+        }
+        .batch()
     }
 }
 
@@ -344,7 +871,7 @@ impl IntoIterator for DynamicBlob {
     type Item = u8;
     #[inline(always)]
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter().batch()
+        SizePrefixed::new(self.0.into_iter()).batch()
     }
 }
 
@@ -354,7 +881,7 @@ impl Decode for Integer {
     type Error = core::convert::Infallible;
     #[inline(always)]
     fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
-        Aligned4B::decode(iter).map(|Aligned4B(a, b, c, d, _)| Self([a, b, c, d]))
+        Self::decode_with_config(iter, DecodeConfig::default())
     }
 }
 
@@ -362,12 +889,22 @@ impl Decode for Float {
     type Error = core::convert::Infallible;
     #[inline(always)]
     fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
-        Aligned4B::decode(iter).map(|Aligned4B(a, b, c, d, _)| Self([a, b, c, d]))
+        Self::decode_with_config(iter, DecodeConfig::default())
+    }
+}
+
+impl Decode for TimeTag {
+    type Error = core::convert::Infallible;
+    /// Two big-endian 32-bit words: seconds, then fractions of a second.
+    #[inline]
+    fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
+        let seconds = u32::from_be_bytes(Integer::decode(iter)?.0);
+        let sub_second = u32::from_be_bytes(Integer::decode(iter)?.0);
+        Ok(Self::new(seconds, sub_second))
     }
 }
 
 #[non_exhaustive]
-#[cfg(feature = "alloc")]
 /// Any possible error while decoding an OSC string.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum StringDecodeErr {
@@ -375,9 +912,12 @@ pub enum StringDecodeErr {
     NonAscii(u8),
     /// Returned a null terminator then the rest of the 4-byte chunk was not null.
     NullThenNonNull,
+    /// Ran out of bytes before the null terminator and its padding.
+    Unterminated,
+    /// Longer than the limit passed to `decode_with_limit`.
+    TooLong,
 }
 
-#[cfg(feature = "alloc")]
 impl core::fmt::Display for StringDecodeErr {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -394,61 +934,121 @@ impl core::fmt::Display for StringDecodeErr {
                 "Matched a string's null terminator, \
                 but the following padding bytes were non-null.",
             ),
+            &Self::Unterminated => write!(
+                f,
+                "Ran out of bytes before an OSC string's null terminator and padding.",
+            ),
+            &Self::TooLong => write!(f, "OSC string exceeds the maximum allowed length."),
         }
     }
 }
 
+/// Decode an OSC string by borrowing from a buffer, without allocating.
+///
+/// Returns the string (without its null terminator) and the number of bytes consumed, including padding.
+/// # Errors
+/// If the string contains non-ASCII characters, has non-null padding, or isn't terminated within `chunks`.
+#[inline]
+pub fn decode_str_into(chunks: &[u8]) -> Result<(&str, usize), StringDecodeErr> {
+    let len = chunks
+        .iter()
+        .position(|&c| c == b'\0')
+        .ok_or(StringDecodeErr::Unterminated)?;
+    // Null terminator plus padding up to the next multiple of 4.
+    #[allow(clippy::arithmetic_side_effects)]
+    // `len` is an index into a slice, so `len | 3` can't be `usize::MAX`.
+    let consumed = (len | 3) + 1;
+    let (Some(bytes), Some(padding)) = (chunks.get(..len), chunks.get(len..consumed)) else {
+        return Err(StringDecodeErr::Unterminated);
+    };
+    if let Some(&c) = bytes.iter().find(|c| !c.is_ascii()) {
+        return Err(StringDecodeErr::NonAscii(c));
+    }
+    if padding.iter().any(|&c| c != b'\0') {
+        return Err(StringDecodeErr::NullThenNonNull);
+    }
+    // SAFETY: Just checked that every byte is ASCII, which is always valid UTF-8.
+    #[allow(unsafe_code)]
+    let s = unsafe { core::str::from_utf8_unchecked(bytes) };
+    Ok((s, consumed))
+}
+
 #[cfg(feature = "alloc")]
 impl Decode for DynamicString {
     type Error = StringDecodeErr;
     #[inline]
     fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
-        let mut s = alloc::string::String::new();
-        loop {
-            let bytes = Aligned4B::decode(iter)?;
-            if bytes.0 == b'\0' {
-                if bytes.1 != b'\0' || bytes.2 != b'\0' || bytes.3 != b'\0' {
-                    return Err(Misaligned4B::Other(StringDecodeErr::NullThenNonNull));
-                }
-                return Ok(Self(s));
-            }
-            if !bytes.0.is_ascii() {
-                return Err(Misaligned4B::Other(StringDecodeErr::NonAscii(bytes.0)));
-            }
-            s.push(char::from(bytes.0));
-            if bytes.1 == b'\0' {
-                if bytes.2 != b'\0' || bytes.3 != b'\0' {
-                    return Err(Misaligned4B::Other(StringDecodeErr::NullThenNonNull));
-                }
-                return Ok(Self(s));
-            }
-            if !bytes.1.is_ascii() {
-                return Err(Misaligned4B::Other(StringDecodeErr::NonAscii(bytes.1)));
-            }
-            s.push(char::from(bytes.1));
-            if bytes.2 == b'\0' {
-                if bytes.3 != b'\0' {
+        decode_string(iter, true, usize::MAX).map(Self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DynamicString {
+    /// Borrow the contents without consuming this string.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+    /// Decode without checking that the padding after the null terminator is also null.
+    ///
+    /// Some non-conforming senders leave garbage in padding bytes, which `decode` rejects.
+    /// This skips the rest of the 4-byte chunk instead, at the cost of silently accepting
+    /// corrupted or misaligned data that `decode` would have caught.
+    /// # Errors
+    /// If the string contains a non-ASCII character or the iterator ends early.
+    #[inline]
+    pub fn decode_lenient<I: Iterator<Item = u8>>(
+        iter: &mut I,
+    ) -> Result<Self, Misaligned4B<StringDecodeErr>> {
+        decode_string(iter, false, usize::MAX).map(Self)
+    }
+    /// Decode, but stop with an error as soon as the string exceeds `max_chars` characters.
+    ///
+    /// Plain `decode` has no limit, so a never-terminating input grows its buffer without bound.
+    /// # Errors
+    /// If the string is too long, contains a non-ASCII character,
+    /// has non-null padding, or the iterator ends early.
+    #[inline]
+    pub fn decode_with_limit<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        max_chars: usize,
+    ) -> Result<Self, Misaligned4B<StringDecodeErr>> {
+        decode_string(iter, true, max_chars).map(Self)
+    }
+}
+
+/// Decode an OSC string of at most `max_chars` characters, optionally checking that its padding is null.
+#[inline]
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+fn decode_string<I: Iterator<Item = u8>>(
+    iter: &mut I,
+    strict: bool,
+    max_chars: usize,
+) -> Result<alloc::string::String, Misaligned4B<StringDecodeErr>> {
+    let mut s = alloc::string::String::new();
+    loop {
+        let mut bytes = Aligned4B::decode(iter)?.into_iter();
+        while let Some(byte) = bytes.next() {
+            if byte == b'\0' {
+                if strict && bytes.any(|c| c != b'\0') {
                     return Err(Misaligned4B::Other(StringDecodeErr::NullThenNonNull));
                 }
-                return Ok(Self(s));
-            }
-            if !bytes.2.is_ascii() {
-                return Err(Misaligned4B::Other(StringDecodeErr::NonAscii(bytes.2)));
+                return Ok(s);
             }
-            s.push(char::from(bytes.2));
-            if bytes.3 == b'\0' {
-                return Ok(Self(s));
+            if !byte.is_ascii() {
+                return Err(Misaligned4B::Other(StringDecodeErr::NonAscii(byte)));
             }
-            if !bytes.3.is_ascii() {
-                return Err(Misaligned4B::Other(StringDecodeErr::NonAscii(bytes.3)));
+            if s.len() >= max_chars {
+                return Err(Misaligned4B::Other(StringDecodeErr::TooLong));
             }
-            s.push(char::from(bytes.3));
+            s.push(char::from(byte));
         }
     }
 }
 
 #[non_exhaustive]
-#[cfg(feature = "alloc")]
 /// Any possible error while decoding an OSC blob.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum BlobDecodeErr {
@@ -458,9 +1058,12 @@ pub enum BlobDecodeErr {
     TooLong,
     /// Returned a null terminator then the rest of the 4-byte chunk was not null.
     NullThenNonNull,
+    /// Declared size exceeds the limit passed to `decode_with_limit`.
+    TooLarge,
+    /// Declared size (plus padding) doesn't fit in this platform's `usize`.
+    SizeOverflow,
 }
 
-#[cfg(feature = "alloc")]
 impl core::fmt::Display for BlobDecodeErr {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -481,39 +1084,180 @@ impl core::fmt::Display for BlobDecodeErr {
                 "Matched a string's null terminator, \
                 but the following padding bytes were non-null.",
             ),
+            &Self::TooLarge => write!(f, "OSC blob size exceeds the maximum allowed."),
+            &Self::SizeOverflow => {
+                write!(f, "OSC blob size is too large to address on this platform.")
+            }
         }
     }
 }
 
+/// Convert a blob's declared size to a length, including padding, without truncating on any platform.
+///
+/// Generic over the length type so the narrow-`usize` case can be checked with e.g. `u16`.
+#[inline]
+pub(crate) fn checked_blob_len<U: TryFrom<usize>>(size: i32) -> Result<(U, U), BlobDecodeErr> {
+    let unsigned = u32::try_from(size).map_err(|_negative| BlobDecodeErr::NegativeSize)?;
+    let unpadded = usize::try_from(unsigned).map_err(|_overflow| BlobDecodeErr::SizeOverflow)?;
+    let padded = padded_len_checked(unpadded).ok_or(BlobDecodeErr::SizeOverflow)?;
+    Ok((
+        U::try_from(unpadded).map_err(|_overflow| BlobDecodeErr::SizeOverflow)?,
+        U::try_from(padded).map_err(|_overflow| BlobDecodeErr::SizeOverflow)?,
+    ))
+}
+
+/// Most bytes a blob will allocate before actually reading them.
+///
+/// Larger blobs are still accepted, but their buffer grows only as bytes arrive,
+/// so a corrupt or malicious size can't trigger a huge allocation up front.
+#[cfg(feature = "alloc")]
+const BLOB_PREALLOCATION_LIMIT: usize = 1 << 16;
+
 #[cfg(feature = "alloc")]
 impl Decode for DynamicBlob {
     type Error = BlobDecodeErr;
     #[inline]
     fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
-        #[allow(unsafe_code)]
-        // SAFETY:
-        // Infallible. Checked at compile time.
-        let size: u32 = i32::from(unsafe { Integer::decode(iter).unwrap_unchecked() })
-            .try_into()
-            .or(Err(Misaligned4B::Other(BlobDecodeErr::NegativeSize)))?;
-        #[allow(clippy::default_numeric_fallback)]
-        let chunks = size >> 3;
-        let mut v = alloc::vec::Vec::with_capacity(chunks.try_into().unwrap_or(0));
-        for _ in 0..chunks {
+        decode_blob(iter, true, usize::MAX, NegativeBlobSize::Reject).map(Self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DynamicBlob {
+    /// Borrow the contents without consuming this blob.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+    /// Number of bytes, not counting the size or padding.
+    #[inline(always)]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Whether there are no bytes at all.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Bytes at this index or range, or `None` if out of bounds.
+    #[inline(always)]
+    #[must_use]
+    pub fn get<R: core::slice::SliceIndex<[u8]>>(&self, index: R) -> Option<&R::Output> {
+        self.0.get(index)
+    }
+    /// Decode without checking that the padding after the contents is null.
+    ///
+    /// See `DynamicString::decode_lenient` for the tradeoff.
+    /// # Errors
+    /// If the size is negative or the iterator ends early.
+    #[inline]
+    pub fn decode_lenient<I: Iterator<Item = u8>>(
+        iter: &mut I,
+    ) -> Result<Self, Misaligned4B<BlobDecodeErr>> {
+        decode_blob(iter, false, usize::MAX, NegativeBlobSize::Reject).map(Self)
+    }
+    /// Decode, but reject any blob whose declared size exceeds `max_len` bytes before allocating anything.
+    ///
+    /// Plain `decode` has no limit (for compatibility), but it never preallocates more than 64 KiB,
+    /// so a bogus size costs at most that much before the input runs out.
+    /// # Errors
+    /// If the size is negative or exceeds `max_len`, the padding is non-null, or the iterator ends early.
+    #[inline]
+    pub fn decode_with_limit<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        max_len: usize,
+    ) -> Result<Self, Misaligned4B<BlobDecodeErr>> {
+        decode_blob(iter, true, max_len, NegativeBlobSize::Reject).map(Self)
+    }
+    /// Decode without collecting, calling `callback` with each 4-byte chunk of the contents as it's read,
+    /// e.g. to stream a large waveform instead of holding all of it at once.
+    ///
+    /// The last chunk is shorter if the size isn't a multiple of 4; padding is checked but never passed along.
+    /// Returns the number of bytes in the blob.
+    /// # Errors
+    /// If the size is negative, the padding is non-null, or the iterator ends early.
+    /// The callback may already have seen some of the contents by then.
+    #[inline]
+    pub fn decode_chunks<I: Iterator<Item = u8>, F: FnMut(&[u8])>(
+        iter: &mut I,
+        mut callback: F,
+    ) -> Result<usize, Misaligned4B<BlobDecodeErr>> {
+        let size = i32::from(Integer::decode(iter).map_err(|e| e.map(|never| match never {}))?);
+        let (len, padded) = checked_blob_len::<usize>(size).map_err(Misaligned4B::Other)?;
+        let mut remaining = len;
+        // Multiple of 4, so nothing lost.
+        for _ in 0..padded >> 2_u32 {
             let bytes = Aligned4B::decode(iter)?;
-            v.push(bytes.0);
-            v.push(bytes.1);
-            v.push(bytes.2);
-            v.push(bytes.3);
+            let chunk = [bytes.0, bytes.1, bytes.2, bytes.3];
+            let (contents, padding) = chunk.split_at(remaining.min(4));
+            if padding.iter().any(|&c| c != b'\0') {
+                return Err(Misaligned4B::Other(BlobDecodeErr::TooLong));
+            }
+            if !contents.is_empty() {
+                callback(contents);
+            }
+            remaining = remaining.saturating_sub(4);
         }
-        Ok(Self(v))
+        Ok(len)
+    }
+    /// Decode, handling a negative size as `config` says.
+    /// # Errors
+    /// If the size is negative (unless `config` clamps it), the padding is non-null, or the iterator ends early.
+    #[inline]
+    pub fn decode_with_config<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+    ) -> Result<Self, Misaligned4B<BlobDecodeErr>> {
+        decode_blob(iter, true, usize::MAX, config.negative_blob_size).map(Self)
+    }
+}
+
+/// Decode an OSC blob of at most `max_len` bytes, optionally checking that its padding is null.
+///
+/// A negative size is handled according to `negative`.
+#[inline]
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+fn decode_blob<I: Iterator<Item = u8>>(
+    iter: &mut I,
+    strict: bool,
+    max_len: usize,
+    negative: NegativeBlobSize,
+) -> Result<alloc::vec::Vec<u8>, Misaligned4B<BlobDecodeErr>> {
+    let mut size = i32::from(Integer::decode(iter).map_err(|e| e.map(|never| match never {}))?);
+    if size < 0_i32 && matches!(negative, NegativeBlobSize::ClampToZero) {
+        size = 0_i32;
+    }
+    let (len, padded) = checked_blob_len::<usize>(size).map_err(Misaligned4B::Other)?;
+    if len > max_len {
+        return Err(Misaligned4B::Other(BlobDecodeErr::TooLarge));
+    }
+    let mut v = alloc::vec::Vec::with_capacity(len.min(BLOB_PREALLOCATION_LIMIT));
+    // Multiple of 4, so nothing lost.
+    for _ in 0..padded >> 2_u32 {
+        let bytes = Aligned4B::decode(iter)?;
+        v.push(bytes.0);
+        v.push(bytes.1);
+        v.push(bytes.2);
+        v.push(bytes.3);
     }
+    if strict
+        && v.get(len..)
+            .is_some_and(|padding| padding.iter().any(|&c| c != b'\0'))
+    {
+        return Err(Misaligned4B::Other(BlobDecodeErr::TooLong));
+    }
+    v.truncate(len);
+    Ok(v)
 }
 
 //////////////// Types that one-to-one map to atomic OSC types
 
 /// Whitelists.
-mod sealed {
+pub(crate) mod sealed {
     /// Whitelist. Otherwise useless.
     pub trait IntoAtomic {}
     impl IntoAtomic for i32 {}
@@ -529,6 +1273,40 @@ mod sealed {
     #[allow(unused_qualifications)]
     #[cfg(feature = "alloc")]
     impl IntoAtomic for alloc::vec::Vec<u8> {}
+
+    /// Any valid value, to stand in for one that failed to convert so that no `unsafe` is needed.
+    pub trait Placeholder {
+        /// Any valid value.
+        fn placeholder() -> Self;
+    }
+    /// Placeholder for each type whose `Default` is valid.
+    macro_rules! placeholder_via_default {
+        ($($t:ty),+) => {
+            $(
+                impl Placeholder for $t {
+                    #[inline(always)]
+                    fn placeholder() -> Self {
+                        Self::default()
+                    }
+                }
+            )+
+        };
+    }
+    placeholder_via_default!(
+        super::Integer,
+        super::Float,
+        super::String<'_>,
+        super::Blob<'_>
+    );
+    #[cfg(feature = "alloc")]
+    placeholder_via_default!(super::DynamicString, super::DynamicBlob);
+    #[cfg(feature = "alloc")]
+    impl Placeholder for crate::Data {
+        #[inline(always)]
+        fn placeholder() -> Self {
+            Self::Integer(super::Integer::default())
+        }
+    }
 }
 
 /// Rust types that map 1-to-1 to atomic OSC types.
@@ -637,6 +1415,21 @@ mod prop {
         }
     }
 
+    impl quickcheck::Arbitrary for TimeTag {
+        #[inline]
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            Self::new(u32::arbitrary(g), u32::arbitrary(g))
+        }
+        #[inline]
+        fn shrink(&self) -> alloc::boxed::Box<dyn Iterator<Item = Self>> {
+            alloc::boxed::Box::new(
+                (self.seconds, self.sub_second)
+                    .shrink()
+                    .map(|(seconds, sub_second)| Self::new(seconds, sub_second)),
+            )
+        }
+    }
+
     #[allow(unused_qualifications)]
     impl quickcheck::Arbitrary for DynamicBlob {
         #[inline]
@@ -9,13 +9,17 @@
 use core::mem::MaybeUninit;
 
 /// Three-byte buffer.
-#[repr(packed)]
+#[repr(C, packed)]
 #[derive(Clone, Copy, Debug)]
 struct Cache {
     /// Three-byte buffer.
     buffer: MaybeUninit<[u8; 3]>,
     /// Index from 0 to 3.
     index: u8,
+    /// One past the last valid index, from 0 to 3.
+    end: u8,
+    /// Number of zeros at the end of the buffer that are padding rather than data, from 0 to 3.
+    padding: u8,
 }
 
 impl Default for Cache {
@@ -25,22 +29,46 @@ impl Default for Cache {
         Self {
             buffer: MaybeUninit::uninit(),
             index: 3,
+            end: 3,
+            padding: 0,
         }
     }
 }
 
 impl Cache {
-    /// Initialize a cache by pulling four bytes, caching the last three and returning the first.
-    fn new<I: Iterator<Item = u8>>(iter: &mut I) -> Self {
+    /// Initialize a cache by pulling three bytes, padding with zeros if the iterator ends,
+    /// but leaving out up to `skip_padding` of those zeros (already yielded from the back).
+    #[allow(clippy::arithmetic_side_effects)]
+    fn new<I: Iterator<Item = u8>>(iter: &mut I, skip_padding: u8) -> Self {
+        let mut padding = 0;
+        let mut pull = || {
+            iter.next().unwrap_or_else(|| {
+                padding += 1;
+                0
+            })
+        };
+        let buffer = [pull(), pull(), pull()];
         Self {
-            buffer: MaybeUninit::new([
-                iter.next().unwrap_or(0),
-                iter.next().unwrap_or(0),
-                iter.next().unwrap_or(0),
-            ]),
+            buffer: MaybeUninit::new(buffer),
             index: 0,
+            // At most 3 of each, so no underflow.
+            end: 3 - padding.min(skip_padding),
+            padding,
         }
     }
+    /// Number of padding zeros not yet yielded.
+    #[inline]
+    #[allow(clippy::arithmetic_side_effects)]
+    const fn remaining_padding(self) -> u8 {
+        // `padding` is at most 3, so no underflow.
+        let first_padding = 3 - self.padding;
+        let start = if self.index > first_padding {
+            self.index
+        } else {
+            first_padding
+        };
+        self.end.saturating_sub(start)
+    }
 }
 
 #[allow(clippy::copy_iterator)]
@@ -49,23 +77,66 @@ impl Iterator for Cache {
     #[inline]
     #[allow(clippy::arithmetic_side_effects, unsafe_code)]
     fn next(&mut self) -> Option<Self::Item> {
-        (self.index < 3).then(|| {
+        (self.index < self.end).then(|| {
             let i = usize::from(self.index);
             self.index += 1;
             // SAFETY:
-            // Just checked above. If `3` ever changes, revisit.
+            // Just checked above, and `end` is at most 3. If `3` ever changes, revisit.
             unsafe { *self.buffer.assume_init().get_unchecked(i) }
         })
     }
 }
 
+impl DoubleEndedIterator for Cache {
+    #[inline]
+    #[allow(clippy::arithmetic_side_effects, unsafe_code)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.index < self.end).then(|| {
+            self.end -= 1;
+            // SAFETY:
+            // Just checked above, and `end` is at most 3. If `3` ever changes, revisit.
+            unsafe {
+                *self
+                    .buffer
+                    .assume_init()
+                    .get_unchecked(usize::from(self.end))
+            }
+        })
+    }
+}
+
+/// Length after padding with zeros to a multiple of 4, e.g. `5` to `8`.
+///
+/// Overflows (panicking in debug) within 3 of `usize::MAX`; for untrusted lengths, see `padded_len_checked`.
+#[inline(always)]
+#[must_use]
+pub const fn padded_len(len: usize) -> usize {
+    len.next_multiple_of(4)
+}
+
+/// Length after padding with zeros to a multiple of 4, or `None` if that doesn't fit in a `usize`.
+#[inline(always)]
+#[must_use]
+pub const fn padded_len_checked(len: usize) -> Option<usize> {
+    len.checked_next_multiple_of(4)
+}
+
 /// Align an iterator to 4-byte batches by padding with zeros at the end.
+///
+/// Each `Batched` pads its own end, so chaining several (e.g. `a.batch().chain(b.batch())`)
+/// finishes padding the first before the second begins, exactly as concatenated OSC values should.
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Batched<I: Iterator<Item = u8>> {
     /// Iterator over individual bytes.
     iter: I,
     /// 4-byte cache.
     cache: Cache,
+    /// Number of padding zeros, then data bytes from the underlying iterator, already yielded from the back.
+    ///
+    /// Either way, those bytes are missing when the final chunk is pulled from the front, so it must skip them.
+    tail: u8,
+    /// Whether all padding at the back has been yielded (and we've moved on to actual data).
+    tail_done: bool,
 }
 
 impl<I: Iterator<Item = u8>> Batched<I> {
@@ -76,8 +147,28 @@ impl<I: Iterator<Item = u8>> Batched<I> {
         Self {
             iter,
             cache: Cache::default(),
+            tail: 0,
+            tail_done: false,
         }
     }
+    /// How many padding zeros are left to yield from the current 4-byte chunk, from 0 to 3.
+    ///
+    /// Only meaningful once the underlying iterator has run out:
+    /// until then, we can't know where the data ends, so this is always 0.
+    #[inline]
+    #[must_use]
+    pub const fn remaining_padding(&self) -> u8 {
+        self.cache.remaining_padding()
+    }
+    /// Whether everything yielded so far fills whole 4-byte chunks, i.e. no padding is still owed.
+    ///
+    /// True before anything has been yielded and again after each chunk's last byte (data or padding),
+    /// so once the stream ends, this means the next value can start right away.
+    #[inline]
+    #[must_use]
+    pub const fn is_padded(&self) -> bool {
+        self.cache.index >= self.cache.end
+    }
     /// Un-batch into the original iterator
     #[inline]
     #[allow(clippy::missing_const_for_fn)]
@@ -93,11 +184,59 @@ impl<I: Iterator<Item = u8>> Iterator for Batched<I> {
         self.cache.next().or_else(|| {
             let tmp = self.iter.next();
             if tmp.is_some() {
-                self.cache = Cache::new(&mut self.iter);
+                self.cache = Cache::new(&mut self.iter, self.tail);
             }
             tmp
         })
     }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Already buffered, including any padding.
+        let cached = usize::from(self.cache.end.saturating_sub(self.cache.index));
+        // Data still in the underlying iterator, plus padding not yet yielded from the back.
+        let pending = |len: usize| {
+            if len == 0 || self.tail_done {
+                Some(len)
+            } else {
+                padded_len_checked(len).map(|padded| padded.saturating_sub(usize::from(self.tail)))
+            }
+        };
+        let (lower, upper) = self.iter.size_hint();
+        (
+            cached.saturating_add(pending(lower).unwrap_or(usize::MAX)),
+            upper
+                .and_then(pending)
+                .and_then(|len| cached.checked_add(len)),
+        )
+    }
+}
+
+impl<I: ExactSizeIterator<Item = u8>> ExactSizeIterator for Batched<I> {}
+
+impl<I: core::iter::FusedIterator<Item = u8>> core::iter::FusedIterator for Batched<I> {}
+
+impl<I: DoubleEndedIterator<Item = u8> + ExactSizeIterator> DoubleEndedIterator for Batched<I> {
+    #[inline]
+    #[allow(clippy::arithmetic_side_effects)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if !self.tail_done {
+            let len = self.iter.len();
+            // Always less than 4, so it fits in a `u8`.
+            #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+            let padding = padded_len(len).saturating_sub(len) as u8;
+            if self.tail < padding {
+                self.tail += 1;
+                return Some(0);
+            }
+            self.tail_done = true;
+        }
+        let from_iter = self.iter.next_back();
+        if from_iter.is_some() {
+            // Only ever compared against at most 3, so saturating is fine.
+            self.tail = self.tail.saturating_add(1);
+        }
+        from_iter.or_else(|| self.cache.next_back())
+    }
 }
 
 /// Call `into_iter` and lazily batch the iterator into four-byte chunks, padding the end with zeros.
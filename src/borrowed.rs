@@ -0,0 +1,333 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Zero-copy views into a buffer holding an entire OSC message.
+
+use crate::{
+    address::split_address, atomic::checked_blob_len, decode_str_into, Address, AddressDecodeErr,
+    Blob, BlobDecodeErr, DynamicDecodeErr, Float, Integer, Message, MessageDecodeErr, Misaligned4B,
+    String, StringDecodeErr, Tag, TagDecodeErr, Tuple,
+};
+
+/// Segments of an address (everything but the method), borrowed from the original buffer.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Segments<'a> {
+    /// Slash-separated segments not yet yielded.
+    rest: &'a str,
+}
+
+impl<'a> Segments<'a> {
+    /// Iterate over the slash-separated segments of a path like `a/b` (no leading slash, no method).
+    #[inline(always)]
+    pub(crate) const fn new(rest: &'a str) -> Self {
+        Self { rest }
+    }
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = &'a str;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let (head, tail) = self.rest.split_once('/').unwrap_or((self.rest, ""));
+        self.rest = tail;
+        Some(head)
+    }
+}
+
+/// Read without consuming, e.g. to encode a decoded message again.
+#[allow(clippy::into_iter_without_iter)]
+impl<'a> IntoIterator for &Segments<'a> {
+    type Item = &'a str;
+    type IntoIter = Segments<'a>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.clone()
+    }
+}
+
+impl<'a> Address<Segments<'a>, &'a str> {
+    /// Path segments before the method, borrowed from the original buffer.
+    #[inline(always)]
+    #[must_use]
+    pub fn segments(&self) -> Segments<'a> {
+        self.0.clone()
+    }
+    /// Final segment, borrowed from the original buffer.
+    #[inline(always)]
+    #[must_use]
+    pub const fn method(&self) -> &'a str {
+        self.1
+    }
+}
+
+/// Single argument borrowed from the original buffer.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum BorrowedData<'a> {
+    /// 32-bit big-endian signed two's-complement integer.
+    Integer(Integer),
+    /// 32-bit big-endian IEEE 754 floating-point number.
+    Float(Float),
+    /// Null-terminated (not your responsibility!) byte string.
+    String(String<'a>),
+    /// Arbitrary known-length collection of bytes.
+    Blob(Blob<'a>),
+    /// Explicitly no value, with no bytes of data (nonstandard in OSC 1.0).
+    #[cfg(feature = "nonstandard-types")]
+    Nil,
+}
+
+impl BorrowedData<'_> {
+    /// OSC type tag of this argument.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub const fn type_tag(self) -> Tag {
+        match self {
+            Self::Integer(_) => Tag::Integer,
+            Self::Float(_) => Tag::Float,
+            Self::String(_) => Tag::String,
+            Self::Blob(_) => Tag::Blob,
+            #[cfg(feature = "nonstandard-types")]
+            Self::Nil => Tag::Nil,
+        }
+    }
+}
+
+/// Arguments to a message, borrowed from the original buffer and already validated.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BorrowedArgs<'a> {
+    /// Type tag characters, without the comma, null terminator, or padding.
+    tags: &'a [u8],
+    /// Encoded arguments, exactly as they appeared in the buffer.
+    data: &'a [u8],
+}
+
+impl<'a> BorrowedArgs<'a> {
+    /// Number of arguments.
+    #[inline(always)]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.tags.len()
+    }
+    /// Whether there are no arguments at all.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+    /// Iterate over every argument in order.
+    #[inline(always)]
+    #[must_use]
+    pub fn iter(&self) -> BorrowedArgsIter<'a> {
+        BorrowedArgsIter {
+            tags: self.tags.iter(),
+            data: self.data,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &BorrowedArgs<'a> {
+    type Item = BorrowedData<'a>;
+    type IntoIter = BorrowedArgsIter<'a>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over arguments borrowed from the original buffer.
+#[derive(Clone, Debug)]
+pub struct BorrowedArgsIter<'a> {
+    /// Type tag characters not yet yielded.
+    tags: core::slice::Iter<'a, u8>,
+    /// Encoded arguments not yet yielded.
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for BorrowedArgsIter<'a> {
+    type Item = BorrowedData<'a>;
+    /// Everything was validated in `decode_borrowed`, so `None` here only ever means the end.
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let tag = Tag::try_from(*self.tags.next()?).ok()?;
+        let (item, consumed) = match tag {
+            Tag::Integer => {
+                let (&bytes, _) = self.data.split_first_chunk::<4>()?;
+                (BorrowedData::Integer(i32::from_be_bytes(bytes).into()), 4)
+            }
+            Tag::Float => {
+                let (&bytes, _) = self.data.split_first_chunk::<4>()?;
+                (BorrowedData::Float(f32::from_be_bytes(bytes).into()), 4)
+            }
+            Tag::String => {
+                let (s, consumed) = decode_str_into(self.data).ok()?;
+                (BorrowedData::String(String::try_from(s).ok()?), consumed)
+            }
+            Tag::Blob => {
+                let (&size, rest) = self.data.split_first_chunk::<4>()?;
+                let (len, padded) = checked_blob_len::<usize>(i32::from_be_bytes(size)).ok()?;
+                let blob = Blob::try_from(rest.get(..len)?).ok()?;
+                (BorrowedData::Blob(blob), padded.checked_add(4)?)
+            }
+            #[cfg(feature = "nonstandard-types")]
+            Tag::Nil => (BorrowedData::Nil, 0),
+            // Rejected by `decode_borrowed`, so never reached.
+            #[cfg(feature = "nonstandard-types")]
+            Tag::ArrayOpen | Tag::ArrayClose => return None,
+        };
+        self.data = self.data.get(consumed..)?;
+        Some(item)
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.tags.size_hint()
+    }
+}
+
+impl<'a> Tuple for BorrowedArgs<'a> {
+    /// Read straight from the type tag string, without touching the arguments.
+    #[inline]
+    fn tag_at(&self, index: usize) -> Option<u8> {
+        self.tags.get(index).copied()
+    }
+    type Chained = core::iter::Copied<core::slice::Iter<'a, u8>>;
+    /// Already encoded, so this just copies bytes out of the original buffer.
+    #[inline]
+    fn chain(self) -> Self::Chained {
+        self.data.iter().copied()
+    }
+    #[inline(always)]
+    fn byte_len(&self) -> Option<usize> {
+        Some(self.data.len())
+    }
+}
+
+/// Running out of bytes means the buffer was truncated; anything else is a genuine error.
+#[inline]
+fn truncated_or<E>(e: StringDecodeErr, f: impl FnOnce(StringDecodeErr) -> E) -> Misaligned4B<E> {
+    match e {
+        StringDecodeErr::Unterminated => Misaligned4B::End,
+        StringDecodeErr::NonAscii(_)
+        | StringDecodeErr::NullThenNonNull
+        | StringDecodeErr::TooLong => Misaligned4B::Other(f(e)),
+    }
+}
+
+/// Split off the next `n` bytes, or report that the buffer ended first.
+#[inline]
+const fn take(buf: &[u8], n: usize) -> Result<(&[u8], &[u8]), Misaligned4B<MessageDecodeErr>> {
+    if buf.len() < n {
+        return Err(Misaligned4B::End);
+    }
+    Ok(buf.split_at(n))
+}
+
+/// Validate the data for each type tag and return how many bytes it spans in total.
+#[inline]
+fn data_len(tags: &[u8], data: &[u8]) -> Result<usize, Misaligned4B<MessageDecodeErr>> {
+    let data_err = |e: DynamicDecodeErr| Misaligned4B::Other(MessageDecodeErr::DataErr(e));
+    let mut rest = data;
+    for (index, &c) in tags.iter().enumerate() {
+        let consumed = match Tag::try_from(c).map_err(|e| data_err(e.into()))? {
+            Tag::Integer | Tag::Float => 4,
+            Tag::String => {
+                decode_str_into(rest)
+                    .map_err(|e| {
+                        truncated_or(e, |err| {
+                            MessageDecodeErr::DataErr(DynamicDecodeErr::StringErr { index, err })
+                        })
+                    })?
+                    .1
+            }
+            Tag::Blob => {
+                let (&size, _) = rest.split_first_chunk::<4>().ok_or(Misaligned4B::End)?;
+                let blob_err = |err| data_err(DynamicDecodeErr::BlobErr { index, err });
+                let (len, padded) =
+                    checked_blob_len::<usize>(i32::from_be_bytes(size)).map_err(blob_err)?;
+                let with_size = padded
+                    .checked_add(4)
+                    .ok_or_else(|| blob_err(BlobDecodeErr::SizeOverflow))?;
+                let (blob, _) = take(rest, with_size)?;
+                if blob.iter().skip(len.saturating_add(4)).any(|&b| b != 0) {
+                    return Err(blob_err(BlobDecodeErr::TooLong));
+                }
+                with_size
+            }
+            #[cfg(feature = "nonstandard-types")]
+            Tag::Nil => 0,
+            #[cfg(feature = "nonstandard-types")]
+            Tag::ArrayOpen | Tag::ArrayClose => {
+                return Err(data_err(TagDecodeErr::ArraysNotYetImplemented(c).into()))
+            }
+        };
+        rest = take(rest, consumed)?.1;
+    }
+    // Only ever removed bytes from the front of `data`.
+    #[allow(clippy::arithmetic_side_effects)]
+    Ok(data.len() - rest.len())
+}
+
+impl<'a> Message<Segments<'a>, &'a str, BorrowedArgs<'a>> {
+    /// Decode a message that sits entirely in this buffer, borrowing from it instead of allocating.
+    ///
+    /// Address segments and the method are `&str`s and arguments are `BorrowedData`,
+    /// all pointing into `buf`, so this works without `alloc`.
+    /// # Errors
+    /// If the message is invalid, if `buf` ends early, or if `buf` continues after the message.
+    #[inline]
+    pub fn decode_borrowed(buf: &'a [u8]) -> Result<Self, Misaligned4B<MessageDecodeErr>> {
+        if !buf.len().is_multiple_of(4) {
+            return Err(Misaligned4B::Misaligned);
+        }
+        let (address, address_len) = decode_str_into(buf).map_err(|e| {
+            truncated_or(e, |s| {
+                MessageDecodeErr::AddressErr(match s {
+                    StringDecodeErr::NonAscii(c) => AddressDecodeErr::NotPrintableAscii(c),
+                    // `TooLong` only comes from explicit limits, which `decode_str_into` doesn't have.
+                    StringDecodeErr::NullThenNonNull
+                    | StringDecodeErr::Unterminated
+                    | StringDecodeErr::TooLong => AddressDecodeErr::NullThenNonNull,
+                })
+            })
+        })?;
+        let (path, method) = split_address(address)
+            .map_err(|e| Misaligned4B::Other(MessageDecodeErr::AddressErr(e)))?;
+        let (_, after_address) = take(buf, address_len)?;
+        let (type_tag, tags_len) = decode_str_into(after_address).map_err(|e| {
+            truncated_or(e, |s| {
+                MessageDecodeErr::DataErr(DynamicDecodeErr::TypeTagErr(match s {
+                    StringDecodeErr::NonAscii(c) => TagDecodeErr::UnrecognizedTypeTag(c),
+                    StringDecodeErr::NullThenNonNull
+                    | StringDecodeErr::Unterminated
+                    | StringDecodeErr::TooLong => TagDecodeErr::NullThenNonNull,
+                }))
+            })
+        })?;
+        let Some(tags) = type_tag.strip_prefix(',') else {
+            return Err(Misaligned4B::Other(MessageDecodeErr::DataErr(
+                DynamicDecodeErr::TypeTagErr(TagDecodeErr::MissingComma(
+                    type_tag.bytes().next().unwrap_or(b'\0'),
+                )),
+            )));
+        };
+        let (_, after_tags) = take(after_address, tags_len)?;
+        let (data, trailing) = take(after_tags, data_len(tags.as_bytes(), after_tags)?)?;
+        if !trailing.is_empty() {
+            return Err(Misaligned4B::TrailingBytes);
+        }
+        Ok(Message::new(
+            Address::from_validated(Segments { rest: path }, method),
+            BorrowedArgs {
+                tags: tags.as_bytes(),
+                data,
+            },
+        ))
+    }
+}
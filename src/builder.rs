@@ -0,0 +1,80 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Build a message one argument at a time.
+
+use crate::{
+    AddressErr, Data, Dynamic, IntoAddress, IntoAtomic, IntoIntoAddress, IntoOsc, InvalidContents,
+    Message,
+};
+use alloc::borrow::ToOwned;
+
+/// Build a message one argument at a time, e.g. when arguments are gathered in a loop.
+/// If you know every argument up front, prefer `.into_osc()` on a tuple.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[allow(clippy::module_name_repetitions)]
+pub struct MessageBuilder<Path: IntoAddress<Method>, Method: IntoIntoAddress> {
+    /// Address path, validated only once we build.
+    path: Path,
+    /// Address method, validated only once we build.
+    method: Method,
+    /// Arguments so far.
+    data: Dynamic,
+}
+
+impl<Path: IntoAddress<Method>, Method: IntoIntoAddress> MessageBuilder<Path, Method> {
+    /// Start a message to this address with no arguments.
+    #[inline]
+    #[must_use]
+    pub fn new(path: Path, method: Method) -> Self {
+        Self {
+            path,
+            method,
+            data: Dynamic::default(),
+        }
+    }
+    /// Append a 32-bit integer argument.
+    #[inline]
+    #[must_use]
+    pub fn add_int(mut self, value: i32) -> Self {
+        let Ok(i) = value.into_atomic();
+        self.data.0.push(Data::Integer(i));
+        self
+    }
+    /// Append a 32-bit floating-point argument.
+    #[inline]
+    #[must_use]
+    pub fn add_float(mut self, value: f32) -> Self {
+        let Ok(f) = value.into_atomic();
+        self.data.0.push(Data::Float(f));
+        self
+    }
+    /// Append a string argument.
+    /// # Errors
+    /// If the string is not valid OSC (e.g. non-ASCII characters).
+    #[inline]
+    pub fn add_string(mut self, value: &str) -> Result<Self, InvalidContents> {
+        self.data
+            .0
+            .push(Data::String(value.to_owned().into_atomic()?));
+        Ok(self)
+    }
+    /// Append a blob argument.
+    /// # Errors
+    /// If the blob is longer than `i32::MAX` bytes.
+    #[inline]
+    pub fn add_blob(mut self, value: &[u8]) -> Result<Self, InvalidContents> {
+        self.data.0.push(Data::Blob(value.to_vec().into_atomic()?));
+        Ok(self)
+    }
+    /// Validate the address and finish the message.
+    /// # Errors
+    /// If the address is invalid (according to the OSC spec).
+    #[inline]
+    pub fn build(self) -> Result<Message<Path, Method, Dynamic>, AddressErr> {
+        self.data.into_osc(self.path, self.method)
+    }
+}
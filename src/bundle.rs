@@ -6,5 +6,319 @@
 
 //! OSC bundle (multiple messages together in a specified format).
 
+use crate::{
+    atomic::check_size_prefix, CountingIter, Decode, DecodeConfig, Integer, InvalidContents,
+    Message, MessageDecodeErr, Misaligned4B, Packet, PacketDecodeErr, TimeTag,
+};
+use alloc::vec::Vec;
+
+/// Every bundle starts with these eight bytes.
+const HEADER: &[u8; 8] = b"#bundle\0";
+
 /// OSC bundle (multiple messages together in a specified format).
-pub struct Bundle();
+///
+/// Elements are either messages or nested bundles, all to be acted on at the same time.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DynamicBundle {
+    /// When to act on this bundle's contents.
+    pub(crate) time_tag: TimeTag,
+    /// Messages and nested bundles.
+    pub(crate) elements: Vec<Packet>,
+}
+
+impl DynamicBundle {
+    /// Bundle these elements together, to be acted on at the given time.
+    /// # Errors
+    /// If any element encodes to more than `i32::MAX` bytes, since its size couldn't be encoded.
+    #[inline]
+    pub fn new(time_tag: TimeTag, elements: Vec<Packet>) -> Result<Self, InvalidContents> {
+        for element in &elements {
+            let _: i32 = check_size_prefix(element.encoded_len())?;
+        }
+        Ok(Self { time_tag, elements })
+    }
+    /// When to act on this bundle's contents.
+    #[inline(always)]
+    #[must_use]
+    pub const fn time_tag(&self) -> TimeTag {
+        self.time_tag
+    }
+    /// Messages and nested bundles.
+    #[inline(always)]
+    #[must_use]
+    pub fn elements(&self) -> &[Packet] {
+        &self.elements
+    }
+    /// Exact number of bytes this bundle will encode to, without encoding it.
+    #[inline]
+    #[must_use]
+    pub fn encoded_len(&self) -> usize {
+        self.elements
+            .iter()
+            // Plus four for each element's size.
+            .map(|element| element.encoded_len().saturating_add(4))
+            // Header plus time tag.
+            .fold(16, usize::saturating_add)
+    }
+    /// Greedily pack elements, in order, into bundles that each encode to at most `mtu` bytes,
+    /// e.g. to send a large scene dump as several UDP datagrams.
+    ///
+    /// Every bundle keeps this bundle's time tag.
+    /// An element too large to fit on its own still gets a bundle of its own, which will exceed `mtu`.
+    /// A bundle with no elements comes back unchanged.
+    #[inline]
+    #[must_use]
+    pub fn split_for_mtu(self, mtu: usize) -> Vec<Self> {
+        let Self { time_tag, elements } = self;
+        if elements.is_empty() {
+            return alloc::vec![Self { time_tag, elements }];
+        }
+        let mut bundles = Vec::new();
+        let mut current = Vec::new();
+        // Header plus time tag.
+        let mut current_len = 16_usize;
+        for element in elements {
+            // Plus four for the element's size.
+            let len = element.encoded_len().saturating_add(4);
+            if !current.is_empty() && current_len.saturating_add(len) > mtu {
+                bundles.push(Self {
+                    time_tag,
+                    elements: core::mem::take(&mut current),
+                });
+                current_len = 16;
+            }
+            current.push(element);
+            current_len = current_len.saturating_add(len);
+        }
+        bundles.push(Self {
+            time_tag,
+            elements: current,
+        });
+        bundles
+    }
+    /// Whether both bundles hold the same elements, in any order (e.g. after a router reordered them).
+    ///
+    /// Elements are compared by their encoded bytes, and time tags are ignored.
+    /// Nested bundles must match exactly, including the order of their own elements.
+    #[inline]
+    #[must_use]
+    pub fn elements_eq_unordered(&self, other: &Self) -> bool {
+        /// References to these elements, sorted by their encoded bytes.
+        fn sorted(elements: &[Packet]) -> Vec<&Packet> {
+            let mut sorted: Vec<&Packet> = elements.iter().collect();
+            sorted.sort_unstable_by(|a, b| a.cmp_encoded(b));
+            sorted
+        }
+        self.elements.len() == other.elements.len()
+            && sorted(&self.elements)
+                .into_iter()
+                .zip(sorted(&other.elements))
+                .all(|(a, b)| a.cmp_encoded(b).is_eq())
+    }
+    /// Compare encoded bytes lexicographically, without encoding either bundle.
+    #[inline]
+    pub(crate) fn cmp_encoded(&self, other: &Self) -> core::cmp::Ordering {
+        self.time_tag.into_iter().cmp(other.time_tag).then_with(|| {
+            for (a, b) in self.elements.iter().zip(&other.elements) {
+                // Sizes are big-endian and never negative, so their bytes sort like the sizes themselves.
+                let ordering = a
+                    .encoded_len()
+                    .cmp(&b.encoded_len())
+                    .then_with(|| a.cmp_encoded(b));
+                if ordering.is_ne() {
+                    return ordering;
+                }
+            }
+            // Whichever ran out of elements first is a prefix of the other.
+            self.elements.len().cmp(&other.elements.len())
+        })
+    }
+    /// Every message to act on by `now`, including those in nested bundles, in order.
+    ///
+    /// Nothing if this bundle's time tag is still in the future (unless it's `immediately`),
+    /// and likewise for each nested bundle.
+    #[inline]
+    pub fn due(&self, now: TimeTag) -> impl Iterator<Item = &Message> {
+        Due {
+            now,
+            stack: if is_due(self.time_tag, now) {
+                alloc::vec![self.elements.iter()]
+            } else {
+                Vec::new()
+            },
+        }
+    }
+    /// Decode, limiting how deeply bundles nest and reading arguments as `config` says.
+    /// # Errors
+    /// If the bundle is invalid, nests deeper than `config.max_depth`, or the iterator ends early.
+    #[inline]
+    pub fn decode_with_config<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+    ) -> Result<Self, Misaligned4B<BundleDecodeErr>> {
+        Self::decode_at_depth(iter, config, 1)
+    }
+    /// Decode a bundle nested `depth` deep (counting a top-level bundle as 1).
+    #[inline]
+    pub(crate) fn decode_at_depth<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+        depth: usize,
+    ) -> Result<Self, Misaligned4B<BundleDecodeErr>> {
+        if depth > config.max_depth {
+            return Err(Misaligned4B::Other(BundleDecodeErr::TooDeep));
+        }
+        for &expected in HEADER {
+            if iter.next().ok_or(Misaligned4B::End)? != expected {
+                return Err(Misaligned4B::Other(BundleDecodeErr::MissingHeader));
+            }
+        }
+        let time_tag = TimeTag::decode(iter).map_err(|e| e.map(Into::into))?;
+        let mut elements = Vec::new();
+        loop {
+            let signed = match Integer::decode(iter) {
+                Ok(size) => i32::from(size),
+                Err(Misaligned4B::End) => return Ok(Self { time_tag, elements }),
+                Err(e) => return Err(e.map(Into::into)),
+            };
+            let size = usize::try_from(signed)
+                .or(Err(Misaligned4B::Other(BundleDecodeErr::NegativeSize)))?;
+            let mut counted = CountingIter::new(iter.by_ref().take(size));
+            // Type-erased so that nested bundles don't instantiate `decode` forever.
+            let mut element: &mut dyn Iterator<Item = u8> = &mut counted;
+            elements.push(
+                Packet::decode_at_depth(&mut element, config, depth.saturating_add(1))
+                    .map_err(|e| e.map(Into::into))?,
+            );
+            if counted.consumed() != size {
+                return Err(Misaligned4B::Other(BundleDecodeErr::SizeMismatch));
+            }
+        }
+    }
+}
+
+/// Whether a bundle with this time tag should be acted on by `now`.
+#[inline]
+fn is_due(time_tag: TimeTag, now: TimeTag) -> bool {
+    time_tag == TimeTag::immediately() || time_tag <= now
+}
+
+/// Depth-first iterator over the messages in due bundles.
+struct Due<'b> {
+    /// When to consider "now."
+    now: TimeTag,
+    /// Remaining elements of each due bundle we're inside, innermost last.
+    stack: Vec<core::slice::Iter<'b, Packet>>,
+}
+
+impl<'b> Iterator for Due<'b> {
+    type Item = &'b Message;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(element) = self.stack.last_mut()?.next() else {
+                let _: Option<_> = self.stack.pop();
+                continue;
+            };
+            match element {
+                &Packet::Message(ref message) => return Some(message),
+                &Packet::Bundle(ref bundle) => {
+                    if is_due(bundle.time_tag, self.now) {
+                        self.stack.push(bundle.elements.iter());
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[allow(unused_qualifications)]
+impl IntoIterator for DynamicBundle {
+    type Item = u8;
+    type IntoIter = alloc::vec::IntoIter<u8>;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let mut v = Vec::with_capacity(self.encoded_len());
+        v.extend_from_slice(HEADER);
+        v.extend(self.time_tag);
+        for element in self.elements {
+            // Checked to fit when the bundle was built.
+            let size = i32::try_from(element.encoded_len()).unwrap_or(i32::MAX);
+            v.extend(Integer::from(size));
+            v.extend(element);
+        }
+        v.into_iter()
+    }
+}
+
+/// Any possible error while decoding an OSC bundle.
+#[non_exhaustive]
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum BundleDecodeErr {
+    /// Didn't start with `#bundle\0`.
+    MissingHeader,
+    /// An element's size was negative.
+    NegativeSize,
+    /// An element's contents didn't match the size it claimed.
+    SizeMismatch,
+    /// An element started with neither `/` (a message) nor `#` (a bundle).
+    UnrecognizedStart(u8),
+    /// Error parsing a message inside this bundle.
+    MessageErr(MessageDecodeErr),
+    /// Bundles were nested deeper than `DecodeConfig::max_depth`.
+    TooDeep,
+}
+
+impl core::fmt::Display for BundleDecodeErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            &Self::MissingHeader => write!(f, "OSC bundle didn't start with \"#bundle\"."),
+            &Self::NegativeSize => write!(f, "OSC bundle element size is negative."),
+            &Self::SizeMismatch => {
+                write!(
+                    f,
+                    "OSC bundle element's contents didn't match the size it claimed."
+                )
+            }
+            &Self::UnrecognizedStart(c) => write!(
+                f,
+                "OSC bundle element started with '{}' instead of '/' or '#'.",
+                core::ascii::escape_default(c)
+            ),
+            &Self::MessageErr(e) => write!(f, "{e}"),
+            &Self::TooDeep => write!(f, "OSC bundles are nested too deeply."),
+        }
+    }
+}
+
+impl From<core::convert::Infallible> for BundleDecodeErr {
+    #[inline(always)]
+    fn from(value: core::convert::Infallible) -> Self {
+        match value {}
+    }
+}
+
+impl From<PacketDecodeErr> for BundleDecodeErr {
+    #[inline]
+    fn from(value: PacketDecodeErr) -> Self {
+        match value {
+            PacketDecodeErr::UnrecognizedStart(c) => Self::UnrecognizedStart(c),
+            PacketDecodeErr::MessageErr(e) => Self::MessageErr(e),
+            PacketDecodeErr::BundleErr(e) => e,
+        }
+    }
+}
+
+impl Decode for DynamicBundle {
+    type Error = BundleDecodeErr;
+    /// Read a header and time tag, then elements until the iterator ends.
+    ///
+    /// Bundles nested more than 32 deep are rejected; see `decode_with_config` to change that.
+    #[inline]
+    fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
+        Self::decode_with_config(iter, DecodeConfig::default())
+    }
+}
@@ -6,8 +6,15 @@
 
 //! Read a stream of bytes into this OSC type or provide a reason we couldn't.
 
+use crate::{
+    AddressCharPolicy, AddressDecodeErr, BlobDecodeErr, MessageDecodeErr, StringDecodeErr,
+    TagDecodeErr,
+};
 use core::marker::PhantomData;
 
+#[cfg(feature = "alloc")]
+use crate::{BundleDecodeErr, PacketDecodeErr, SchemaMismatch};
+
 /// Read a stream of bytes into this OSC type or provide a reason we couldn't.
 pub trait Decode: Sized {
     /// Reasons this might fail.
@@ -16,6 +23,282 @@ pub trait Decode: Sized {
     /// # Errors
     /// If the stream's length is not a multiple of 4 or if we encounter any issues along the way.
     fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>>;
+    /// Like `decode`, but on failure also report the byte offset at which parsing stopped.
+    ///
+    /// For `Other` errors, this is the start of the 4-byte chunk in which the error was found;
+    /// for `End` and `Misaligned`, it's the number of bytes read before the input ran out.
+    /// # Errors
+    /// If `decode` would fail.
+    #[inline]
+    fn decode_at<I: Iterator<Item = u8>>(
+        iter: &mut I,
+    ) -> Result<Self, (usize, Misaligned4B<Self::Error>)> {
+        let mut counting = CountingIter::new(iter);
+        Self::decode(&mut counting).map_err(|e| {
+            let count = counting.consumed();
+            match e {
+                Misaligned4B::Other(_) => (count.saturating_sub(1) & !3, e),
+                Misaligned4B::End | Misaligned4B::Misaligned | Misaligned4B::TrailingBytes => {
+                    (count, e)
+                }
+            }
+        })
+    }
+    /// Decode exactly this buffer, failing if any bytes remain after a complete parse.
+    /// # Errors
+    /// If `decode` would fail or if `bytes` continues afterward.
+    #[inline]
+    fn decode_slice(bytes: &[u8]) -> Result<Self, Misaligned4B<Self::Error>> {
+        let mut iter = bytes.iter().copied();
+        let decoded = Self::decode(&mut iter)?;
+        if iter.next().is_some() {
+            return Err(Misaligned4B::TrailingBytes);
+        }
+        Ok(decoded)
+    }
+}
+
+/// Iterator adapter that counts how many items have been consumed.
+#[derive(Clone, Debug)]
+pub struct CountingIter<I: Iterator> {
+    /// Underlying iterator.
+    iter: I,
+    /// Number of items consumed so far.
+    count: usize,
+}
+
+impl<I: Iterator> CountingIter<I> {
+    /// Start counting from zero.
+    #[inline(always)]
+    pub const fn new(iter: I) -> Self {
+        Self { iter, count: 0 }
+    }
+    /// Number of items consumed so far.
+    #[inline(always)]
+    #[must_use]
+    pub const fn consumed(&self) -> usize {
+        self.count
+    }
+}
+
+impl<I: Iterator> Iterator for CountingIter<I> {
+    type Item = I::Item;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        self.count = self.count.saturating_add(1);
+        Some(item)
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Decoder fed bytes in arbitrary chunks (e.g. from TCP), reporting "need more" instead of failing on truncation.
+///
+/// `T` must know where it ends on its own (e.g. `Message`, but not a bare bundle),
+/// and every `push` re-parses the buffered bytes from the start, so keep chunks reasonably large.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct IncrementalDecoder<T: Decode> {
+    /// Bytes received but not yet part of a complete item.
+    #[allow(unused_qualifications)]
+    buffer: alloc::vec::Vec<u8>,
+    /// Type we're decoding, without owning one.
+    output: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Decode> IncrementalDecoder<T> {
+    /// Start with an empty buffer.
+    #[inline(always)]
+    #[must_use]
+    #[allow(unused_qualifications)]
+    pub const fn new() -> Self {
+        Self {
+            buffer: alloc::vec::Vec::new(),
+            output: PhantomData,
+        }
+    }
+    /// Number of bytes received but not yet part of a complete item.
+    #[inline(always)]
+    #[must_use]
+    pub const fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+    /// Append these bytes, then return the next complete item if there is one, or `None` if we need more.
+    ///
+    /// Each call returns at most one item; if a chunk held several, call again with an empty slice.
+    /// # Errors
+    /// If the buffered bytes can never become a valid item, no matter what comes next.
+    #[inline]
+    pub fn push(&mut self, bytes: &[u8]) -> Result<Option<T>, T::Error> {
+        self.buffer.extend_from_slice(bytes);
+        let mut iter = CountingIter::new(self.buffer.iter().copied());
+        match T::decode(&mut iter) {
+            Ok(item) => {
+                let consumed = iter.consumed();
+                drop(self.buffer.drain(..consumed));
+                Ok(Some(item))
+            }
+            Err(Misaligned4B::End | Misaligned4B::Misaligned | Misaligned4B::TrailingBytes) => {
+                Ok(None)
+            }
+            Err(Misaligned4B::Other(e)) => Err(e),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Decode> Default for IncrementalDecoder<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Version of the OSC spec whose type tags to accept while decoding.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum OscVersion {
+    /// OSC 1.0: only `i`, `f`, `s`, and `b`; anything else (e.g. `N`) is rejected.
+    V1_0,
+    /// OSC 1.1: also the types it made standard, of which this crate supports `N`.
+    #[default]
+    V1_1,
+}
+
+/// Byte order of integers and floats.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Endian {
+    /// Most significant byte first, as the OSC spec requires.
+    #[default]
+    Big,
+    /// Least significant byte first: never valid OSC, but useful to diagnose a sender that byte-swaps.
+    Little,
+}
+
+/// What to do with a blob whose declared size has the high bit set (i.e. is negative).
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum NegativeBlobSize {
+    /// Fail with `BlobDecodeErr::NegativeSize`, since it almost surely means the input is misaligned.
+    #[default]
+    Reject,
+    /// Treat it as an empty blob and keep going, for senders known to write garbage sizes.
+    ClampToZero,
+}
+
+/// What to do with a message whose arguments don't start with a type tag string (i.e. a comma).
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum MissingTypeTags {
+    /// Fail with `TagDecodeErr::MissingComma` (or `End` if the message stops after its address).
+    #[default]
+    Reject,
+    /// Treat a message that ends right after its address as having no arguments, as old OSC 1.0 senders intend.
+    /// Anything else after the address still needs type tags, since there's no telling where it ends;
+    /// to leave it for the next decoder instead, see `Dynamic::decode_allow_untyped`.
+    NoArguments,
+}
+
+/// Deepest a bundle can be nested by default before decoding gives up.
+const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// Options controlling what a decoder accepts.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DecodeConfig {
+    /// Version of the OSC spec whose type tags to accept.
+    pub version: OscVersion,
+    /// Byte order of integers and floats (big-endian unless debugging a nonconforming sender).
+    pub endianness: Endian,
+    /// Whether a negative blob size is an error or an empty blob.
+    pub negative_blob_size: NegativeBlobSize,
+    /// Deepest a bundle can be nested (counting a top-level bundle as 1), to bound recursion on untrusted input.
+    pub max_depth: usize,
+    /// Whether a message without type tags is an error or has no arguments.
+    pub missing_type_tags: MissingTypeTags,
+    /// Which characters a message's address may contain.
+    pub address_chars: AddressCharPolicy,
+}
+
+impl Default for DecodeConfig {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(OscVersion::default())
+    }
+}
+
+impl DecodeConfig {
+    /// Accept exactly the type tags standard in this version of the OSC spec.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(version: OscVersion) -> Self {
+        Self {
+            version,
+            endianness: Endian::Big,
+            negative_blob_size: NegativeBlobSize::Reject,
+            max_depth: DEFAULT_MAX_DEPTH,
+            missing_type_tags: MissingTypeTags::Reject,
+            address_chars: AddressCharPolicy::Strict,
+        }
+    }
+    /// Read integers and floats in this byte order instead.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_endianness(self, endianness: Endian) -> Self {
+        Self { endianness, ..self }
+    }
+    /// Handle negative blob sizes this way instead.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_negative_blob_size(self, negative_blob_size: NegativeBlobSize) -> Self {
+        Self {
+            negative_blob_size,
+            ..self
+        }
+    }
+    /// Allow bundles nested at most this deep (32 by default).
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+    /// Handle messages without type tags this way instead.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_missing_type_tags(self, missing_type_tags: MissingTypeTags) -> Self {
+        Self {
+            missing_type_tags,
+            ..self
+        }
+    }
+    /// Accept address characters under this policy instead.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_address_chars(self, address_chars: AddressCharPolicy) -> Self {
+        Self {
+            address_chars,
+            ..self
+        }
+    }
+}
+
+/// Advance past exactly `n` bytes, e.g. a length prefix or transport tag before an OSC packet.
+/// # Errors
+/// If the iterator ends before `n` bytes.
+#[inline]
+pub fn skip_prefix<I: Iterator<Item = u8>>(
+    iter: &mut I,
+    n: usize,
+) -> Result<(), Misaligned4B<core::convert::Infallible>> {
+    if iter.by_ref().take(n).count() < n {
+        return Err(Misaligned4B::End);
+    }
+    Ok(())
 }
 
 /// Anywhere we could read a number of bytes not a multiple of four.
@@ -28,6 +311,278 @@ pub enum Misaligned4B<E> {
     Misaligned,
     /// Number of bytes was a multiple of 4, but another error occurred.
     Other(E),
+    /// Parsed successfully, but the input continued afterward.
+    TrailingBytes,
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for Misaligned4B<E> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            &Self::End => write!(f, "OSC data ended when we expected more input."),
+            &Self::Misaligned => write!(
+                f,
+                "OSC data ended partway through a 4-byte chunk (length not a multiple of 4)."
+            ),
+            &Self::Other(ref e) => write!(f, "{e}"),
+            &Self::TrailingBytes => write!(f, "OSC data continued after a complete parse."),
+        }
+    }
+}
+
+impl<E> Misaligned4B<E> {
+    /// Convert the error in `Other`, if any, leaving the other variants untouched.
+    #[inline]
+    pub fn map<F, G: FnOnce(E) -> F>(self, f: G) -> Misaligned4B<F> {
+        match self {
+            Self::End => Misaligned4B::End,
+            Self::Misaligned => Misaligned4B::Misaligned,
+            Self::TrailingBytes => Misaligned4B::TrailingBytes,
+            Self::Other(e) => Misaligned4B::Other(f(e)),
+        }
+    }
+    /// Whether more bytes might fix this (i.e. the input just ended early),
+    /// as opposed to the input being malformed no matter what follows.
+    ///
+    /// Stream decoders can wait for more data on `true` and drop the packet on `false`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_incomplete(&self) -> bool {
+        matches!(*self, Self::End)
+    }
+}
+
+/// Any possible errors while parsing an OSC message of unknown structure.
+///
+/// Running out of input (`Misaligned4B::End`) is never blamed on any one argument,
+/// so a truncated string or blob carries no index.
+#[non_exhaustive]
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DynamicDecodeErr {
+    /// Error parsing type tags.
+    TypeTagErr(TagDecodeErr),
+    /// Error parsing a string argument.
+    StringErr {
+        /// Position of the offending argument, counting from zero.
+        index: usize,
+        /// What was wrong with it.
+        err: StringDecodeErr,
+    },
+    /// Error parsing a blob argument.
+    BlobErr {
+        /// Position of the offending argument, counting from zero.
+        index: usize,
+        /// What was wrong with it.
+        err: BlobDecodeErr,
+    },
+    /// Type tags were valid but not the ones passed to `Dynamic::decode_expecting`.
+    #[cfg(feature = "alloc")]
+    Schema(SchemaMismatch),
+}
+
+impl core::fmt::Display for DynamicDecodeErr {
+    #[inline(always)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            &DynamicDecodeErr::TypeTagErr(e) => write!(f, "{e}"),
+            &DynamicDecodeErr::StringErr { index, err } => {
+                write!(f, "Argument {index} (a string) was malformed: {err}")
+            }
+            &DynamicDecodeErr::BlobErr { index, err } => {
+                write!(f, "Argument {index} (a blob) was malformed: {err}")
+            }
+            #[cfg(feature = "alloc")]
+            &DynamicDecodeErr::Schema(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<core::convert::Infallible> for DynamicDecodeErr {
+    #[inline(always)]
+    fn from(value: core::convert::Infallible) -> Self {
+        match value {}
+    }
+}
+
+impl From<TagDecodeErr> for DynamicDecodeErr {
+    #[inline]
+    fn from(value: TagDecodeErr) -> Self {
+        Self::TypeTagErr(value)
+    }
+}
+
+/// Blames the first argument, since a bare `StringDecodeErr` doesn't say which one it came from.
+impl From<StringDecodeErr> for DynamicDecodeErr {
+    #[inline]
+    fn from(err: StringDecodeErr) -> Self {
+        Self::StringErr { index: 0, err }
+    }
+}
+
+/// Blames the first argument, since a bare `BlobDecodeErr` doesn't say which one it came from.
+impl From<BlobDecodeErr> for DynamicDecodeErr {
+    #[inline]
+    fn from(err: BlobDecodeErr) -> Self {
+        Self::BlobErr { index: 0, err }
+    }
+}
+
+/// Any error from any decoder in this crate, flattened into one type, e.g. for `?` in a receive loop.
+///
+/// Every specific error type (and `Misaligned4B` of it) converts into this with `From`,
+/// so callers needn't match on which part of a packet failed.
+/// Converting forgets which argument a `DynamicDecodeErr` blamed.
+#[non_exhaustive]
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DecodeErr {
+    /// Error parsing an address.
+    Address(AddressDecodeErr),
+    /// Error parsing type tags.
+    Tags(TagDecodeErr),
+    /// Error parsing a string argument.
+    String(StringDecodeErr),
+    /// Error parsing a blob argument.
+    Blob(BlobDecodeErr),
+    /// Error in a bundle's own structure (not in one of its messages).
+    #[cfg(feature = "alloc")]
+    Bundle(BundleDecodeErr),
+    /// Arguments didn't have the types the caller expected.
+    #[cfg(feature = "alloc")]
+    Schema(SchemaMismatch),
+    /// Packet started with neither `/` (a message) nor `#` (a bundle).
+    UnrecognizedStart(u8),
+    /// Number of bytes was not a multiple of 4.
+    Misaligned,
+    /// Ended when we expected more input.
+    End,
+    /// Parsed successfully, but the input continued afterward.
+    TrailingBytes,
+}
+
+impl core::fmt::Display for DecodeErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            &Self::Address(e) => write!(f, "{e}"),
+            &Self::Tags(e) => write!(f, "{e}"),
+            &Self::String(e) => write!(f, "{e}"),
+            &Self::Blob(e) => write!(f, "{e}"),
+            #[cfg(feature = "alloc")]
+            &Self::Bundle(e) => write!(f, "{e}"),
+            #[cfg(feature = "alloc")]
+            &Self::Schema(e) => write!(f, "{e}"),
+            &Self::UnrecognizedStart(c) => write!(
+                f,
+                "OSC packet started with '{}' instead of '/' or '#'.",
+                core::ascii::escape_default(c)
+            ),
+            &Self::Misaligned => write!(f, "{}", Misaligned4B::<Self>::Misaligned),
+            &Self::End => write!(f, "{}", Misaligned4B::<Self>::End),
+            &Self::TrailingBytes => write!(f, "{}", Misaligned4B::<Self>::TrailingBytes),
+        }
+    }
+}
+
+impl<E> From<Misaligned4B<E>> for DecodeErr
+where
+    DecodeErr: From<E>,
+{
+    #[inline]
+    fn from(value: Misaligned4B<E>) -> Self {
+        match value {
+            Misaligned4B::End => Self::End,
+            Misaligned4B::Misaligned => Self::Misaligned,
+            Misaligned4B::TrailingBytes => Self::TrailingBytes,
+            Misaligned4B::Other(e) => e.into(),
+        }
+    }
+}
+
+impl From<core::convert::Infallible> for DecodeErr {
+    #[inline(always)]
+    fn from(value: core::convert::Infallible) -> Self {
+        match value {}
+    }
+}
+
+impl From<AddressDecodeErr> for DecodeErr {
+    #[inline(always)]
+    fn from(value: AddressDecodeErr) -> Self {
+        Self::Address(value)
+    }
+}
+
+impl From<TagDecodeErr> for DecodeErr {
+    #[inline(always)]
+    fn from(value: TagDecodeErr) -> Self {
+        Self::Tags(value)
+    }
+}
+
+impl From<StringDecodeErr> for DecodeErr {
+    #[inline(always)]
+    fn from(value: StringDecodeErr) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<BlobDecodeErr> for DecodeErr {
+    #[inline(always)]
+    fn from(value: BlobDecodeErr) -> Self {
+        Self::Blob(value)
+    }
+}
+
+impl From<DynamicDecodeErr> for DecodeErr {
+    #[inline]
+    fn from(value: DynamicDecodeErr) -> Self {
+        match value {
+            DynamicDecodeErr::TypeTagErr(e) => Self::Tags(e),
+            DynamicDecodeErr::StringErr { err, .. } => Self::String(err),
+            DynamicDecodeErr::BlobErr { err, .. } => Self::Blob(err),
+            #[cfg(feature = "alloc")]
+            DynamicDecodeErr::Schema(e) => Self::Schema(e),
+        }
+    }
+}
+
+impl From<MessageDecodeErr> for DecodeErr {
+    #[inline]
+    fn from(value: MessageDecodeErr) -> Self {
+        match value {
+            MessageDecodeErr::AddressErr(e) => Self::Address(e),
+            MessageDecodeErr::DataErr(e) => e.into(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<BundleDecodeErr> for DecodeErr {
+    #[inline]
+    fn from(value: BundleDecodeErr) -> Self {
+        match value {
+            BundleDecodeErr::UnrecognizedStart(c) => Self::UnrecognizedStart(c),
+            BundleDecodeErr::MessageErr(e) => e.into(),
+            BundleDecodeErr::MissingHeader
+            | BundleDecodeErr::NegativeSize
+            | BundleDecodeErr::SizeMismatch
+            | BundleDecodeErr::TooDeep => Self::Bundle(value),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<PacketDecodeErr> for DecodeErr {
+    #[inline]
+    fn from(value: PacketDecodeErr) -> Self {
+        match value {
+            PacketDecodeErr::UnrecognizedStart(c) => Self::UnrecognizedStart(c),
+            PacketDecodeErr::MessageErr(e) => e.into(),
+            PacketDecodeErr::BundleErr(e) => e.into(),
+        }
+    }
 }
 
 /// Four bytes read at the same time.
@@ -7,10 +7,14 @@
 //! OSC values whose types can't be known at compile time.
 
 use crate::{
-    Aligned4B, Batch, Batched, Decode, DynamicBlob, DynamicString, Float, Integer, Misaligned4B,
-    Tag, TagDecodeErr,
+    atomic::checked_blob_len, skip_prefix, Aligned4B, Atomic, Batch, Batched, Blob, CountingIter,
+    Decode, DecodeConfig, DynamicBlob, DynamicDecodeErr, DynamicString, Float, Integer,
+    InvalidContents, Misaligned4B, MissingTypeTags, String, StringDecodeErr, Tag, TagDecodeErr,
 };
 
+/// Most type tags `Tags::decode` will accept before giving up.
+const DEFAULT_MAX_TAGS: usize = 1 << 16;
+
 /// Unknown number of OSC type tags.
 #[repr(transparent)]
 #[allow(unused_qualifications)]
@@ -29,73 +33,126 @@ impl IntoIterator for Tags {
         >,
     >;
     #[inline]
-    #[allow(clippy::as_conversions, clippy::as_underscore, trivial_casts)]
     fn into_iter(self) -> Self::IntoIter {
+        let to_byte: fn(Tag) -> u8 = u8::from;
         core::iter::once(b',')
-            .chain(self.0.into_iter().map((|c: Tag| c as u8) as _))
+            .chain(self.0.into_iter().map(to_byte))
             .chain(core::iter::once(b'\0'))
             .batch()
     }
 }
 
-#[allow(unused_qualifications)]
-impl Decode for Tags {
-    type Error = TagDecodeErr;
+impl<'t> IntoIterator for &'t Tags {
+    type Item = &'t Tag;
+    type IntoIter = core::slice::Iter<'t, Tag>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Tags {
+    /// Iterate over the type tags themselves, unlike `into_iter`, which yields their encoded bytes.
+    #[inline(always)]
+    pub fn iter(&self) -> core::slice::Iter<'_, Tag> {
+        self.0.iter()
+    }
+    /// Consume into the type tags themselves, unlike `into_iter`, which yields their encoded bytes.
+    #[inline(always)]
+    #[must_use]
+    #[allow(unused_qualifications)]
+    pub fn into_tags(self) -> alloc::vec::IntoIter<Tag> {
+        self.0.into_iter()
+    }
+    /// Parse a type tag string like `",ifsb"`, without null termination or padding.
+    /// # Errors
+    /// If the string doesn't start with a comma or contains an unrecognized type tag character.
     #[inline]
-    fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
-        let mut v = alloc::vec![];
-        {
-            let first = Aligned4B::decode(iter)?;
-            if first.0 != b',' {
-                return Err(Misaligned4B::Other(TagDecodeErr::MissingComma(first.0)));
-            }
-            if first.1 == b'\0' {
-                if first.2 != b'\0' || first.3 != b'\0' {
-                    return Err(Misaligned4B::Other(TagDecodeErr::NullThenNonNull));
-                }
-                return Ok(Self(v));
-            }
-            v.push(first.1.try_into().map_err(Misaligned4B::Other)?);
-            if first.2 == b'\0' {
-                if first.3 != b'\0' {
-                    return Err(Misaligned4B::Other(TagDecodeErr::NullThenNonNull));
-                }
-                return Ok(Self(v));
-            }
-            v.push(first.2.try_into().map_err(Misaligned4B::Other)?);
-            if first.3 == b'\0' {
-                return Ok(Self(v));
-            }
-            v.push(first.3.try_into().map_err(Misaligned4B::Other)?);
+    pub fn parse(s: &str) -> Result<Self, TagDecodeErr> {
+        let mut bytes = s.bytes();
+        match bytes.next() {
+            Some(b',') => bytes.map(Tag::try_from).collect::<Result<_, _>>().map(Self),
+            other => Err(TagDecodeErr::MissingComma(other.unwrap_or(b'\0'))),
         }
-        loop {
-            let bytes = Aligned4B::decode(iter)?;
-            if bytes.0 == b'\0' {
-                if bytes.1 != b'\0' || bytes.2 != b'\0' || bytes.3 != b'\0' {
-                    return Err(Misaligned4B::Other(TagDecodeErr::NullThenNonNull));
+    }
+    /// Decode, but stop with an error as soon as there are more than `max_tags` type tags.
+    ///
+    /// Otherwise, a long enough run of valid tag characters grows the buffer without bound
+    /// before a single argument is read.
+    /// # Errors
+    /// If there are too many tags, the comma is missing, a tag is unrecognized,
+    /// the padding is non-null, or the iterator ends early.
+    #[inline]
+    #[allow(unused_qualifications)]
+    pub fn decode_with_limit<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        max_tags: usize,
+    ) -> Result<Self, Misaligned4B<TagDecodeErr>> {
+        let mut v = alloc::vec![];
+        decode_tag_bytes(iter, max_tags, |byte| {
+            v.push(byte.try_into()?);
+            Ok(())
+        })?;
+        Ok(Self(v))
+    }
+    /// Advance past the arguments these tags describe without decoding them, e.g. to forward them verbatim.
+    ///
+    /// Fixed-size arguments are skipped by `Tag::payload_size`; strings are skipped up to their null terminator
+    /// and blobs by their size prefix. Contents aren't checked beyond what's needed to find where each ends.
+    /// Returns the number of bytes skipped.
+    /// # Errors
+    /// If a string's padding is non-null, a blob's size is negative, or the iterator ends early.
+    #[inline]
+    pub fn skip_arguments<I: Iterator<Item = u8>>(
+        &self,
+        iter: &mut I,
+    ) -> Result<usize, Misaligned4B<DynamicDecodeErr>> {
+        let mut skipped = 0_usize;
+        for (index, &tag) in self.0.iter().enumerate() {
+            let len = match tag.payload_size() {
+                Some(size) => {
+                    skip_prefix(iter, size).map_err(|e| e.map(|never| match never {}))?;
+                    size
                 }
-                return Ok(Self(v));
-            }
-            v.push(bytes.0.try_into().map_err(Misaligned4B::Other)?);
-            if bytes.1 == b'\0' {
-                if bytes.2 != b'\0' || bytes.3 != b'\0' {
-                    return Err(Misaligned4B::Other(TagDecodeErr::NullThenNonNull));
+                None if tag == Tag::String => {
+                    let mut len = 0_usize;
+                    loop {
+                        let mut chunk = Aligned4B::<DynamicDecodeErr>::decode(iter)?.into_iter();
+                        len = len.saturating_add(4);
+                        if chunk.any(|c| c == b'\0') {
+                            if chunk.any(|c| c != b'\0') {
+                                return Err(Misaligned4B::Other(DynamicDecodeErr::StringErr {
+                                    index,
+                                    err: StringDecodeErr::NullThenNonNull,
+                                }));
+                            }
+                            break len;
+                        }
+                    }
                 }
-                return Ok(Self(v));
-            }
-            v.push(bytes.1.try_into().map_err(Misaligned4B::Other)?);
-            if bytes.2 == b'\0' {
-                if bytes.3 != b'\0' {
-                    return Err(Misaligned4B::Other(TagDecodeErr::NullThenNonNull));
+                None => {
+                    let size = i32::from(
+                        Integer::decode(iter).map_err(|e| e.map(|never| match never {}))?,
+                    );
+                    let (_, padded) = checked_blob_len::<usize>(size).map_err(|err| {
+                        Misaligned4B::Other(DynamicDecodeErr::BlobErr { index, err })
+                    })?;
+                    skip_prefix(iter, padded).map_err(|e| e.map(|never| match never {}))?;
+                    padded.saturating_add(4)
                 }
-                return Ok(Self(v));
-            }
-            v.push(bytes.2.try_into().map_err(Misaligned4B::Other)?);
-            if bytes.3 == b'\0' {
-                return Ok(Self(v));
-            }
-            v.push(bytes.3.try_into().map_err(Misaligned4B::Other)?);
+            };
+            skipped = skipped.saturating_add(len);
         }
+        Ok(skipped)
+    }
+}
+
+impl Decode for Tags {
+    type Error = TagDecodeErr;
+    /// Decode with a generous limit of 65,536 tags; see `decode_with_limit`.
+    #[inline]
+    fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
+        Self::decode_with_limit(iter, DEFAULT_MAX_TAGS)
     }
 }
 
@@ -115,6 +172,7 @@ impl quickcheck::Arbitrary for Tags {
 /// OSC values whose types can't be known at compile time.
 #[non_exhaustive]
 #[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Data {
     /// 32-bit big-endian signed two's-complement integer.
@@ -125,8 +183,188 @@ pub enum Data {
     String(DynamicString),
     /// Arbitrary known-length collection of bytes.
     Blob(DynamicBlob),
+    /// Explicitly no value, with no bytes of data (nonstandard in OSC 1.0).
+    #[cfg(feature = "nonstandard-types")]
+    Nil,
+    /// Argument with a type tag this crate doesn't recognize, kept as-is by `Dynamic::decode_skipping_unknown`.
+    Unknown(UnknownArg),
+}
+
+/// Argument with a type tag this crate doesn't recognize, with its encoded bytes exactly as read.
+///
+/// Only constructed by `UnknownArg::new` or `Dynamic::decode_skipping_unknown`, so it always encodes
+/// to a well-formed argument.
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct UnknownArg {
+    /// Type tag character: never null, and never one `Tag` recognizes.
+    pub(crate) tag: u8,
+    /// Encoded bytes, a multiple of 4 long.
+    pub(crate) bytes: alloc::vec::Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl UnknownArg {
+    /// Wrap an argument whose type tag this crate doesn't recognize.
+    /// # Errors
+    /// If `tag` is null or recognized by `Tag`, or if `bytes` isn't a multiple of 4 long.
+    #[inline]
+    #[allow(unused_qualifications)]
+    pub fn new(tag: u8, bytes: alloc::vec::Vec<u8>) -> Result<Self, UnknownArgErr> {
+        if tag == b'\0' {
+            return Err(UnknownArgErr::NullTag);
+        }
+        if let Ok(known) = Tag::try_from(tag) {
+            return Err(UnknownArgErr::RecognizedTag(known));
+        }
+        if !bytes.len().is_multiple_of(4) {
+            return Err(UnknownArgErr::Misaligned);
+        }
+        Ok(Self { tag, bytes })
+    }
+    /// Type tag character.
+    #[inline(always)]
+    #[must_use]
+    pub const fn tag(&self) -> u8 {
+        self.tag
+    }
+    /// Encoded bytes, exactly as read.
+    #[inline(always)]
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Why `UnknownArg::new` refused to wrap an argument.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum UnknownArgErr {
+    /// A null tag would end the type tag string early.
+    NullTag,
+    /// This tag is recognized, so its argument should be decoded as `Data` instead.
+    RecognizedTag(Tag),
+    /// Arguments occupy whole 4-byte chunks, but this one didn't.
+    Misaligned,
+}
+
+impl core::fmt::Display for UnknownArgErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            &Self::NullTag => write!(f, "Type tag of an unknown OSC argument can't be null."),
+            &Self::RecognizedTag(tag) => {
+                write!(
+                    f,
+                    "Type tag '{tag}' is recognized, so its argument isn't unknown."
+                )
+            }
+            &Self::Misaligned => write!(f, "OSC arguments must occupy a multiple of 4 bytes."),
+        }
+    }
+}
+
+/// Iterator over the OSC-formatted bytes of any `Data` without an intermediate allocation.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub enum DataIter {
+    /// Iterator over a 32-bit big-endian signed two's-complement integer.
+    Integer(<Integer as Atomic>::Iter),
+    /// Iterator over a 32-bit big-endian IEEE 754 floating-point number.
+    Float(<Float as Atomic>::Iter),
+    /// Iterator over a null-terminated byte string.
+    String(<DynamicString as Atomic>::Iter),
+    /// Iterator over an arbitrary known-length collection of bytes.
+    Blob(<DynamicBlob as Atomic>::Iter),
+    /// Empty iterator over no value.
+    #[cfg(feature = "nonstandard-types")]
+    Nil,
+    /// Iterator over an unrecognized argument's bytes, exactly as read.
+    Unknown(alloc::vec::IntoIter<u8>),
+}
+
+impl Iterator for DataIter {
+    type Item = u8;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            &mut Self::Integer(ref mut i) => i.next(),
+            &mut Self::Float(ref mut f) => f.next(),
+            &mut Self::String(ref mut s) => s.next(),
+            &mut Self::Blob(ref mut b) => b.next(),
+            #[cfg(feature = "nonstandard-types")]
+            &mut Self::Nil => None,
+            &mut Self::Unknown(ref mut u) => u.next(),
+        }
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            &Self::Integer(ref i) => i.size_hint(),
+            &Self::Float(ref f) => f.size_hint(),
+            &Self::String(ref s) => s.size_hint(),
+            &Self::Blob(ref b) => b.size_hint(),
+            #[cfg(feature = "nonstandard-types")]
+            &Self::Nil => (0, Some(0)),
+            &Self::Unknown(ref u) => u.size_hint(),
+        }
+    }
 }
 
+impl core::iter::FusedIterator for DataIter {}
+
+/// Iterator over the OSC-formatted bytes of a borrowed `Data`, without cloning it.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub enum DataRefIter<'d> {
+    /// Iterator over a 32-bit big-endian signed two's-complement integer.
+    Integer(<Integer as Atomic>::Iter),
+    /// Iterator over a 32-bit big-endian IEEE 754 floating-point number.
+    Float(<Float as Atomic>::Iter),
+    /// Iterator over a borrowed null-terminated byte string.
+    String(<String<'d> as Atomic>::Iter),
+    /// Iterator over a borrowed arbitrary known-length collection of bytes.
+    Blob(<Blob<'d> as Atomic>::Iter),
+    /// Empty iterator over no value.
+    #[cfg(feature = "nonstandard-types")]
+    Nil,
+    /// Iterator over an unrecognized argument's borrowed bytes, exactly as read.
+    Unknown(core::iter::Copied<core::slice::Iter<'d, u8>>),
+}
+
+impl Iterator for DataRefIter<'_> {
+    type Item = u8;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            &mut Self::Integer(ref mut i) => i.next(),
+            &mut Self::Float(ref mut f) => f.next(),
+            &mut Self::String(ref mut s) => s.next(),
+            &mut Self::Blob(ref mut b) => b.next(),
+            #[cfg(feature = "nonstandard-types")]
+            &mut Self::Nil => None,
+            &mut Self::Unknown(ref mut u) => u.next(),
+        }
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            &Self::Integer(ref i) => i.size_hint(),
+            &Self::Float(ref f) => f.size_hint(),
+            &Self::String(ref s) => s.size_hint(),
+            &Self::Blob(ref b) => b.size_hint(),
+            #[cfg(feature = "nonstandard-types")]
+            &Self::Nil => (0, Some(0)),
+            &Self::Unknown(ref u) => u.size_hint(),
+        }
+    }
+}
+
+impl core::iter::FusedIterator for DataRefIter<'_> {}
+
 impl TryFrom<Data> for Integer {
     type Error = Data;
     #[inline(always)]
@@ -175,69 +413,565 @@ impl TryFrom<Data> for DynamicBlob {
     }
 }
 
+impl TryFrom<Data> for i32 {
+    type Error = Data;
+    #[inline(always)]
+    fn try_from(value: Data) -> Result<Self, Self::Error> {
+        Integer::try_from(value).map(Into::into)
+    }
+}
+
+impl TryFrom<Data> for f32 {
+    type Error = Data;
+    #[inline(always)]
+    fn try_from(value: Data) -> Result<Self, Self::Error> {
+        Float::try_from(value).map(Into::into)
+    }
+}
+
+#[allow(unused_qualifications)]
+impl TryFrom<Data> for alloc::string::String {
+    type Error = Data;
+    #[inline(always)]
+    fn try_from(value: Data) -> Result<Self, Self::Error> {
+        DynamicString::try_from(value).map(Into::into)
+    }
+}
+
+#[allow(unused_qualifications)]
+impl TryFrom<Data> for alloc::vec::Vec<u8> {
+    type Error = Data;
+    #[inline(always)]
+    fn try_from(value: Data) -> Result<Self, Self::Error> {
+        DynamicBlob::try_from(value).map(Into::into)
+    }
+}
+
+impl From<Integer> for Data {
+    #[inline(always)]
+    fn from(value: Integer) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<Float> for Data {
+    #[inline(always)]
+    fn from(value: Float) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<DynamicString> for Data {
+    #[inline(always)]
+    fn from(value: DynamicString) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<DynamicBlob> for Data {
+    #[inline(always)]
+    fn from(value: DynamicBlob) -> Self {
+        Self::Blob(value)
+    }
+}
+
+impl From<i32> for Data {
+    #[inline(always)]
+    fn from(value: i32) -> Self {
+        Self::Integer(value.into())
+    }
+}
+
+impl From<f32> for Data {
+    #[inline(always)]
+    fn from(value: f32) -> Self {
+        Self::Float(value.into())
+    }
+}
+
 /// Vector of data whose types are unknown at compile time.
 #[repr(transparent)]
 #[allow(unused_qualifications)]
 #[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Dynamic(pub(crate) alloc::vec::Vec<Data>);
 
-/// Any possible errors while parsing an OSC message of unknown structure.
-#[non_exhaustive]
-#[allow(clippy::module_name_repetitions)]
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum DynamicDecodeErr {
-    /// Error parsing type tags.
-    TypeTagErr(TagDecodeErr),
+impl Dynamic {
+    /// Number of arguments.
+    #[inline(always)]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Whether there are no arguments at all.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// The argument at this index, if it exists and is an integer.
+    #[inline]
+    #[must_use]
+    pub fn get_int(&self, index: usize) -> Option<i32> {
+        match self.0.get(index) {
+            Some(&Data::Integer(i)) => Some(i.into()),
+            _ => None,
+        }
+    }
+    /// The argument at this index, if it exists and is a float.
+    #[inline]
+    #[must_use]
+    pub fn get_float(&self, index: usize) -> Option<f32> {
+        match self.0.get(index) {
+            Some(&Data::Float(f)) => Some(f.into()),
+            _ => None,
+        }
+    }
+    /// The argument at this index, if it exists and is a string.
+    #[inline]
+    #[must_use]
+    pub fn get_str(&self, index: usize) -> Option<&str> {
+        match self.0.get(index) {
+            Some(&Data::String(ref s)) => Some(&s.0),
+            _ => None,
+        }
+    }
+    /// The argument at this index, if it exists and is a blob.
+    #[inline]
+    #[must_use]
+    pub fn get_blob(&self, index: usize) -> Option<&[u8]> {
+        match self.0.get(index) {
+            Some(&Data::Blob(ref b)) => Some(&b.0),
+            _ => None,
+        }
+    }
+    /// Iterate over every argument in order.
+    #[inline(always)]
+    pub fn iter(&self) -> core::slice::Iter<'_, Data> {
+        self.0.iter()
+    }
+    /// Type tag of each argument in order, e.g. `b"ifs"` for `,ifs`.
+    #[inline]
+    pub fn tags(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.iter().map(Atomic::type_tag)
+    }
+    /// Append an argument.
+    #[inline(always)]
+    pub fn push(&mut self, data: Data) {
+        self.0.push(data);
+    }
+    /// Append an integer argument.
+    #[inline(always)]
+    pub fn push_int(&mut self, i: i32) {
+        self.push(i.into());
+    }
+    /// Append a float argument.
+    #[inline(always)]
+    pub fn push_float(&mut self, f: f32) {
+        self.push(f.into());
+    }
+    /// Append a string argument.
+    /// # Errors
+    /// If the string is not ASCII or contains a null byte.
+    #[inline]
+    #[allow(unused_qualifications)]
+    pub fn push_str(&mut self, s: &str) -> Result<(), InvalidContents> {
+        self.push(DynamicString::try_from(alloc::borrow::ToOwned::to_owned(s))?.into());
+        Ok(())
+    }
+    /// Append a blob argument.
+    /// # Errors
+    /// If the blob is longer than `i32::MAX` bytes.
+    #[inline]
+    pub fn push_blob(&mut self, b: &[u8]) -> Result<(), InvalidContents> {
+        self.push(DynamicBlob::try_from(b.to_vec())?.into());
+        Ok(())
+    }
+    /// Convert into a tuple of Rust types, e.g. `(i32, f32)` for `,if`, if the arguments match exactly.
+    /// # Errors
+    /// If the number of arguments differs or any argument has the wrong type.
+    #[inline(always)]
+    pub fn extract<T: FromDynamic>(self) -> Result<T, SchemaMismatch> {
+        T::from_dynamic(self)
+    }
+    /// Call the `visitor` method matching each argument's type, in order.
+    #[inline]
+    pub fn accept<V: DataVisitor>(&self, visitor: &mut V) {
+        for data in &self.0 {
+            match data {
+                &Data::Integer(i) => visitor.visit_int(i.into()),
+                &Data::Float(f) => visitor.visit_float(f.into()),
+                &Data::String(ref s) => visitor.visit_str(s.as_ref()),
+                &Data::Blob(ref b) => visitor.visit_blob(b.as_slice()),
+                #[cfg(feature = "nonstandard-types")]
+                &Data::Nil => visitor.visit_nil(),
+                &Data::Unknown(ref u) => visitor.visit_unknown(u.tag(), u.bytes()),
+            }
+        }
+    }
+    /// Remove and return the argument at this index, shifting everything after it down by one.
+    ///
+    /// `None` if the index is out of bounds.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> Option<Data> {
+        (index < self.0.len()).then(|| self.0.remove(index))
+    }
+    /// Decode, rejecting any type tag that isn't standard in `config.version`
+    /// and reading integers and floats in `config.endianness`.
+    /// # Errors
+    /// If the type tags or any argument is invalid, or if the iterator ends early.
+    #[inline]
+    pub fn decode_with_config<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+    ) -> Result<Self, Misaligned4B<DynamicDecodeErr>> {
+        if matches!(config.missing_type_tags, MissingTypeTags::Reject) {
+            return Self::decode_typed(iter, config);
+        }
+        let mut counting = CountingIter::new(iter);
+        match Self::decode_typed(&mut counting, config) {
+            Err(Misaligned4B::End) if counting.consumed() == 0 => Ok(Self::default()),
+            other => other,
+        }
+    }
+    /// Decode, treating a message without type tags (e.g. from an old OSC 1.0 sender) as having no arguments.
+    ///
+    /// Peeks at the next byte without consuming it, so if it isn't a comma, nothing is read:
+    /// whatever follows (e.g. the next message in a stream) is left for the next decoder.
+    /// # Errors
+    /// If the type tags (when present) or any argument is invalid, or if the iterator ends early.
+    #[inline]
+    pub fn decode_allow_untyped<I: Iterator<Item = u8>>(
+        iter: &mut core::iter::Peekable<I>,
+    ) -> Result<Self, Misaligned4B<DynamicDecodeErr>> {
+        if iter.peek() == Some(&b',') {
+            Self::decode_typed(iter, DecodeConfig::default())
+        } else {
+            Ok(Self::default())
+        }
+    }
+    /// Decode, failing as soon as the type tags are read if they aren't exactly `expected`,
+    /// before parsing any arguments.
+    /// # Errors
+    /// If the type tags don't match `expected` (as a `DynamicDecodeErr::Schema`),
+    /// if the type tags or any argument is invalid, or if the iterator ends early.
+    #[inline]
+    pub fn decode_expecting<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        expected: &Tags,
+    ) -> Result<Self, Misaligned4B<DynamicDecodeErr>> {
+        let types = Tags::decode(iter).map_err(|e| e.map(DynamicDecodeErr::TypeTagErr))?;
+        if types.0.len() != expected.0.len() {
+            return Err(Misaligned4B::Other(DynamicDecodeErr::Schema(
+                SchemaMismatch::Count {
+                    expected: expected.0.len(),
+                    actual: types.0.len(),
+                },
+            )));
+        }
+        if let Some((index, &actual)) = types
+            .0
+            .iter()
+            .zip(&expected.0)
+            .enumerate()
+            .find_map(|(index, (actual, wanted))| (actual != wanted).then_some((index, actual)))
+        {
+            return Err(Misaligned4B::Other(DynamicDecodeErr::Schema(
+                SchemaMismatch::Type {
+                    index,
+                    actual: actual.as_byte(),
+                },
+            )));
+        }
+        Self::decode_arguments(types, iter, DecodeConfig::default())
+    }
+    /// Decode type tags, which must be present, and then the arguments they describe.
+    #[inline]
+    fn decode_typed<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+    ) -> Result<Self, Misaligned4B<DynamicDecodeErr>> {
+        let types = Tags::decode(iter).map_err(|e| e.map(DynamicDecodeErr::TypeTagErr))?;
+        Self::decode_arguments(types, iter, config)
+    }
+    /// Decode the arguments described by already-decoded type tags.
+    #[inline]
+    #[allow(unused_qualifications)]
+    fn decode_arguments<I: Iterator<Item = u8>>(
+        types: Tags,
+        iter: &mut I,
+        config: DecodeConfig,
+    ) -> Result<Self, Misaligned4B<DynamicDecodeErr>> {
+        if let Some(&tag) = types
+            .0
+            .iter()
+            .find(|tag| !tag.is_standard_in(config.version))
+        {
+            return Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(
+                TagDecodeErr::NotInVersion(tag.as_byte()),
+            )));
+        }
+        let mut v = alloc::vec::Vec::with_capacity(types.0.len());
+        for (index, tag) in types.0.into_iter().enumerate() {
+            v.push(decode_data(tag, index, iter, config)?);
+        }
+        Ok(Self(v))
+    }
+    /// Decode, but keep arguments with unrecognized type tags as `Data::Unknown` instead of failing,
+    /// as long as `fixed_size` knows how many bytes each one occupies (e.g. `Some(8)` for `b'h'`).
+    ///
+    /// Unrecognized tags for which `fixed_size` returns `None` (e.g. variable-length types)
+    /// or a size that isn't a multiple of 4 still fail, since there's no way to know where the next argument starts.
+    /// # Errors
+    /// If the type tags or any argument is invalid, or if the iterator ends early.
+    #[inline]
+    #[allow(unused_qualifications)]
+    pub fn decode_skipping_unknown<I: Iterator<Item = u8>, F: Fn(u8) -> Option<usize>>(
+        iter: &mut I,
+        fixed_size: F,
+    ) -> Result<Self, Misaligned4B<DynamicDecodeErr>> {
+        let mut tags = alloc::vec![];
+        decode_tag_bytes(iter, DEFAULT_MAX_TAGS, |byte| {
+            tags.push(byte);
+            Ok(())
+        })
+        .map_err(|e| e.map(DynamicDecodeErr::TypeTagErr))?;
+        let mut v = alloc::vec::Vec::with_capacity(tags.len());
+        for (index, c) in tags.into_iter().enumerate() {
+            match Tag::try_from(c) {
+                Ok(tag) => v.push(decode_data(tag, index, iter, DecodeConfig::default())?),
+                Err(e) => {
+                    let size = fixed_size(c)
+                        .filter(|size| size.is_multiple_of(4))
+                        .ok_or_else(|| Misaligned4B::Other(e.into()))?;
+                    let bytes: alloc::vec::Vec<u8> = iter.by_ref().take(size).collect();
+                    if bytes.len() != size {
+                        return Err(Misaligned4B::End);
+                    }
+                    v.push(Data::Unknown(UnknownArg { tag: c, bytes }));
+                }
+            }
+        }
+        Ok(Self(v))
+    }
 }
 
-impl core::fmt::Display for DynamicDecodeErr {
+impl<'a> IntoIterator for &'a Dynamic {
+    type Item = &'a Data;
+    type IntoIter = core::slice::Iter<'a, Data>;
     #[inline(always)]
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        match self {
-            &DynamicDecodeErr::TypeTagErr(e) => write!(f, "{e}"),
-        }
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Data> for Dynamic {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = Data>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
     }
 }
 
-impl From<TagDecodeErr> for DynamicDecodeErr {
+impl Extend<Data> for Dynamic {
     #[inline]
-    fn from(value: TagDecodeErr) -> Self {
-        Self::TypeTagErr(value)
+    fn extend<T: IntoIterator<Item = Data>>(&mut self, iter: T) {
+        self.0.extend(iter);
     }
 }
 
 #[allow(unused_qualifications)]
 impl Decode for Dynamic {
     type Error = DynamicDecodeErr;
+    /// Accepts every type tag this crate supports; see `decode_with_config` to restrict them.
     #[inline]
     fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
-        let types = match Tags::decode(iter) {
-            Ok(ok) => ok,
-            Err(Misaligned4B::End) => return Err(Misaligned4B::End),
-            Err(Misaligned4B::Misaligned) => return Err(Misaligned4B::Misaligned),
-            Err(Misaligned4B::Other(o)) => {
-                return Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(o)))
-            }
-        };
-        let mut v = alloc::vec::Vec::with_capacity(types.0.len());
-        for tag in types.0 {
-            #[allow(unsafe_code, unused_unsafe)]
-            // TODO:
-            // SAFETY:
-            // Uncertain. Revisit after property testing.
-            v.push(unsafe {
-                match tag {
-                    Tag::Integer => Data::Integer(Integer::decode(iter).unwrap_unchecked()),
-                    Tag::Float => Data::Float(Float::decode(iter).unwrap_unchecked()),
-                    Tag::String => Data::String(DynamicString::decode(iter).unwrap_unchecked()),
-                    Tag::Blob => Data::Blob(DynamicBlob::decode(iter).unwrap_unchecked()),
+        Self::decode_with_config(iter, DecodeConfig::default())
+    }
+}
+
+/// Read a type tag string, handing each character after the comma to `each`,
+/// but failing once there are more than `max_tags` of them.
+#[inline]
+fn decode_tag_bytes<I: Iterator<Item = u8>, F: FnMut(u8) -> Result<(), TagDecodeErr>>(
+    iter: &mut I,
+    max_tags: usize,
+    mut each: F,
+) -> Result<(), Misaligned4B<TagDecodeErr>> {
+    let mut bytes = Aligned4B::decode(iter)?.into_iter();
+    let comma = bytes.next().unwrap_or(b'\0');
+    if comma != b',' {
+        return Err(Misaligned4B::Other(TagDecodeErr::MissingComma(comma)));
+    }
+    let mut count = 0_usize;
+    loop {
+        while let Some(byte) = bytes.next() {
+            if byte == b'\0' {
+                if bytes.any(|c| c != b'\0') {
+                    return Err(Misaligned4B::Other(TagDecodeErr::NullThenNonNull));
                 }
-            });
+                return Ok(());
+            }
+            if count >= max_tags {
+                return Err(Misaligned4B::Other(TagDecodeErr::TooMany));
+            }
+            count = count.saturating_add(1);
+            each(byte).map_err(Misaligned4B::Other)?;
+        }
+        bytes = Aligned4B::decode(iter)?.into_iter();
+    }
+}
+
+/// Decode a single argument whose type is already known, blaming the argument at `index` for any error.
+#[inline]
+fn decode_data<I: Iterator<Item = u8>>(
+    tag: Tag,
+    index: usize,
+    iter: &mut I,
+    config: DecodeConfig,
+) -> Result<Data, Misaligned4B<DynamicDecodeErr>> {
+    Ok(match tag {
+        Tag::Integer => {
+            Data::Integer(Integer::decode_with_config(iter, config).map_err(|e| e.map(Into::into))?)
+        }
+        Tag::Float => {
+            Data::Float(Float::decode_with_config(iter, config).map_err(|e| e.map(Into::into))?)
+        }
+        Tag::String => Data::String(
+            DynamicString::decode(iter)
+                .map_err(|e| e.map(|err| DynamicDecodeErr::StringErr { index, err }))?,
+        ),
+        Tag::Blob => Data::Blob(
+            DynamicBlob::decode_with_config(iter, config)
+                .map_err(|e| e.map(|err| DynamicDecodeErr::BlobErr { index, err }))?,
+        ),
+        #[cfg(feature = "nonstandard-types")]
+        Tag::Nil => Data::Nil,
+        #[cfg(feature = "nonstandard-types")]
+        Tag::ArrayOpen | Tag::ArrayClose => {
+            return Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(
+                TagDecodeErr::ArraysNotYetImplemented(tag.as_byte()),
+            )))
+        }
+    })
+}
+
+/// Arguments didn't match the types expected by `Dynamic::extract` or `Dynamic::decode_expecting`.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum SchemaMismatch {
+    /// Wrong number of arguments.
+    Count {
+        /// Number of arguments expected.
+        expected: usize,
+        /// Number of arguments received.
+        actual: usize,
+    },
+    /// An argument had the wrong type.
+    Type {
+        /// Position of the offending argument.
+        index: usize,
+        /// Type tag of the offending argument.
+        actual: u8,
+    },
+}
+
+impl core::fmt::Display for SchemaMismatch {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            &Self::Count { expected, actual } => write!(
+                f,
+                "Expected {expected} OSC arguments but received {actual}."
+            ),
+            &Self::Type { index, actual } => {
+                write!(
+                    f,
+                    "OSC argument #{index} had unexpected type '{}'.",
+                    core::ascii::escape_default(actual)
+                )
+            }
         }
-        Ok(Self(v))
     }
 }
 
+/// Handlers for each type of argument, called in order by `Dynamic::accept`.
+pub trait DataVisitor {
+    /// Handle an integer argument.
+    fn visit_int(&mut self, i: i32);
+    /// Handle a floating-point argument.
+    fn visit_float(&mut self, f: f32);
+    /// Handle a string argument.
+    fn visit_str(&mut self, s: &str);
+    /// Handle a blob argument.
+    fn visit_blob(&mut self, b: &[u8]);
+    /// Handle a nil argument, which has no value; ignored unless overridden.
+    #[inline(always)]
+    #[cfg(feature = "nonstandard-types")]
+    fn visit_nil(&mut self) {}
+    /// Handle an argument with an unrecognized type tag (see `Data::Unknown`); ignored unless overridden.
+    #[inline(always)]
+    fn visit_unknown(&mut self, _tag: u8, _bytes: &[u8]) {}
+}
+
+/// Tuples of Rust types that can be pulled out of a `Dynamic` whose types match exactly.
+///
+/// The decode-side counterpart to `IntoOsc` on tuples.
+pub trait FromDynamic: Sized {
+    /// Convert, if every argument has the expected type.
+    /// # Errors
+    /// If the number of arguments differs or any argument has the wrong type.
+    fn from_dynamic(dynamic: Dynamic) -> Result<Self, SchemaMismatch>;
+}
+
+/// Convert the next argument, reporting its position if it has the wrong type.
+#[inline]
+#[allow(unused_qualifications)]
+fn extract_next<T: TryFrom<Data, Error = Data>>(
+    iter: &mut core::iter::Enumerate<alloc::vec::IntoIter<Data>>,
+    expected: usize,
+) -> Result<T, SchemaMismatch> {
+    let (index, data) = iter.next().ok_or(SchemaMismatch::Count {
+        expected,
+        actual: 0,
+    })?;
+    T::try_from(data).map_err(|wrong| SchemaMismatch::Type {
+        index,
+        actual: wrong.type_tag(),
+    })
+}
+
+/// Implement `FromDynamic` for a tuple of types, each of which can be converted from `Data`.
+macro_rules! impl_from_dynamic {
+    ($n:expr, $($id:ident),+) => {
+        impl<$($id: TryFrom<Data, Error = Data>),+> FromDynamic for ($($id,)+) {
+            #[inline]
+            fn from_dynamic(dynamic: Dynamic) -> Result<Self, SchemaMismatch> {
+                if dynamic.0.len() != $n {
+                    return Err(SchemaMismatch::Count { expected: $n, actual: dynamic.0.len() });
+                }
+                let mut iter = dynamic.0.into_iter().enumerate();
+                Ok(($(extract_next::<$id>(&mut iter, $n)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_dynamic!(1, A);
+impl_from_dynamic!(2, A, B);
+impl_from_dynamic!(3, A, B, C);
+impl_from_dynamic!(4, A, B, C, D);
+impl_from_dynamic!(5, A, B, C, D, E);
+impl_from_dynamic!(6, A, B, C, D, E, F);
+impl_from_dynamic!(7, A, B, C, D, E, F, G);
+impl_from_dynamic!(8, A, B, C, D, E, F, G, H);
+impl_from_dynamic!(9, A, B, C, D, E, F, G, H, I);
+impl_from_dynamic!(10, A, B, C, D, E, F, G, H, I, J);
+impl_from_dynamic!(11, A, B, C, D, E, F, G, H, I, J, K);
+impl_from_dynamic!(12, A, B, C, D, E, F, G, H, I, J, K, L);
+impl_from_dynamic!(13, A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_from_dynamic!(14, A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_from_dynamic!(15, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_from_dynamic!(16, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
 #[cfg(feature = "quickcheck")]
 #[allow(unused_qualifications)]
 impl quickcheck::Arbitrary for Data {
@@ -268,10 +1002,44 @@ impl quickcheck::Arbitrary for Data {
             &Self::Float(ref f) => alloc::boxed::Box::new(f.shrink().map(Self::Float)),
             &Self::String(ref s) => alloc::boxed::Box::new(s.shrink().map(Self::String)),
             &Self::Blob(ref b) => alloc::boxed::Box::new(b.shrink().map(Self::Blob)),
+            #[cfg(feature = "nonstandard-types")]
+            &Self::Nil => quickcheck::empty_shrinker(),
+            &Self::Unknown(_) => quickcheck::empty_shrinker(),
         }
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Data {
+    #[inline]
+    #[allow(unused_qualifications)]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match Tag::arbitrary(u)? {
+            Tag::Integer => Self::Integer(i32::arbitrary(u)?.into()),
+            Tag::Float => Self::Float(f32::arbitrary(u)?.into()),
+            Tag::String => {
+                let mut s = alloc::string::String::arbitrary(u)?;
+                s.retain(|c| c.is_ascii() && c != '\0');
+                Self::String(DynamicString(s))
+            }
+            Tag::Blob => Self::Blob(DynamicBlob(u.arbitrary()?)),
+            #[cfg(feature = "nonstandard-types")]
+            Tag::Nil => Self::Nil,
+            // Never chosen by `Tag::arbitrary`, since brackets aren't values on their own.
+            #[cfg(feature = "nonstandard-types")]
+            Tag::ArrayOpen | Tag::ArrayClose => Self::Nil,
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Dynamic {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Self)
+    }
+}
+
 #[cfg(feature = "quickcheck")]
 #[allow(unused_qualifications)]
 impl quickcheck::Arbitrary for Dynamic {
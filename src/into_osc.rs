@@ -7,8 +7,8 @@
 //! Format a Rust type as an OSC message.
 
 use crate::{
-    AddressErr, Blob, Float, Integer, IntoAddress, IntoAtomic, IntoIntoAddress, InvalidContents,
-    Message, String, Tuple,
+    atomic::sealed::Placeholder, AddressErr, Blob, Float, Integer, IntoAddress, IntoAtomic,
+    IntoIntoAddress, InvalidContents, Message, String, Tuple,
 };
 
 #[cfg(feature = "alloc")]
@@ -46,6 +46,42 @@ impl IntoOsc for i32 {
     }
 }
 
+/// Implement `IntoOsc` for an integer type that always fits in an `i32`.
+macro_rules! impl_for_narrow_int {
+    ($($t:ty),+) => {$(
+        impl IntoOsc for $t {
+            type AsOsc = (Integer,);
+            #[inline(always)]
+            fn into_osc<Path: IntoAddress<Method>, Method: IntoIntoAddress>(
+                self,
+                path: Path,
+                method: Method,
+            ) -> Result<Message<Path, Method, Self::AsOsc>, AddressErr> {
+                Ok(Message::new(path.into_address(method)?, (Integer::from_i32(i32::from(self)),)))
+            }
+        }
+    )+};
+}
+
+impl_for_narrow_int!(i8, i16, u8, u16);
+
+/// OSC has no unsigned integers, so the bits are reinterpreted as two's complement:
+/// anything above `i32::MAX` arrives negative (e.g. `u32::MAX` as `-1`).
+impl IntoOsc for u32 {
+    type AsOsc = (Integer,);
+    #[inline(always)]
+    fn into_osc<Path: IntoAddress<Method>, Method: IntoIntoAddress>(
+        self,
+        path: Path,
+        method: Method,
+    ) -> Result<Message<Path, Method, Self::AsOsc>, AddressErr> {
+        Ok(Message::new(
+            path.into_address(method)?,
+            (Integer::from_i32(i32::from_be_bytes(self.to_be_bytes())),),
+        ))
+    }
+}
+
 impl IntoOsc for f32 {
     type AsOsc = (Float,);
     #[inline(always)]
@@ -88,9 +124,7 @@ impl<'b> IntoOsc for &'b [u8] {
     ) -> Result<Message<Path, Method, Self::AsOsc>, AddressErr> {
         Ok(Message::new(
             path.into_address(method)?,
-            (self
-                .into_atomic()
-                .map_err(|e| AddressErr::StringErr(e.into()))?,),
+            (self.into_atomic().map_err(AddressErr::StringErr)?,),
         ))
     }
 }
@@ -107,6 +141,27 @@ impl IntoOsc for () {
     }
 }
 
+#[cfg(feature = "nonstandard-types")]
+impl<T: IntoAtomic> IntoOsc for Option<T>
+where
+    InvalidContents: From<<T::AsAtomic as TryFrom<T>>::Error>,
+{
+    type AsOsc = Option<T::AsAtomic>;
+    #[inline(always)]
+    fn into_osc<Path: IntoAddress<Method>, Method: IntoIntoAddress>(
+        self,
+        path: Path,
+        method: Method,
+    ) -> Result<Message<Path, Method, Self::AsOsc>, AddressErr> {
+        Ok(Message::new(
+            path.into_address(method)?,
+            self.map(IntoAtomic::into_atomic)
+                .transpose()
+                .map_err(|e| AddressErr::StringErr(e.into()))?,
+        ))
+    }
+}
+
 /// Implement `IntoOsc` for a tuple of types, each of which implement `IntoAtomic`.
 macro_rules! impl_for_tuple {
     ($($id:ident),+) => {
@@ -137,6 +192,67 @@ impl_for_tuple!(A, B, C, D, E);
 impl_for_tuple!(A, B, C, D, E, F);
 impl_for_tuple!(A, B, C, D, E, F, G);
 impl_for_tuple!(A, B, C, D, E, F, G, H);
+impl_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+impl<T: IntoAtomic, const N: usize> IntoOsc for [T; N]
+where
+    InvalidContents: From<<T::AsAtomic as TryFrom<T>>::Error>,
+    T::AsAtomic: Placeholder,
+{
+    type AsOsc = [T::AsAtomic; N];
+    #[inline]
+    fn into_osc<Path: IntoAddress<Method>, Method: IntoIntoAddress>(
+        self,
+        path: Path,
+        method: Method,
+    ) -> Result<Message<Path, Method, Self::AsOsc>, AddressErr> {
+        let address = path.into_address(method)?;
+        let mut first_err = None;
+        let atomics = self.map(|e| {
+            e.into_atomic().unwrap_or_else(|err| {
+                let _: &mut InvalidContents = first_err.get_or_insert_with(|| err.into());
+                Placeholder::placeholder()
+            })
+        });
+        if let Some(e) = first_err {
+            return Err(AddressErr::StringErr(e));
+        }
+        Ok(Message::new(address, atomics))
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl<T: IntoAtomic + Clone> IntoOsc for &[T]
+where
+    InvalidContents: From<<T::AsAtomic as TryFrom<T>>::Error>,
+{
+    type AsOsc = alloc::vec::Vec<T::AsAtomic>;
+    #[inline]
+    fn into_osc<Path: IntoAddress<Method>, Method: IntoIntoAddress>(
+        self,
+        path: Path,
+        method: Method,
+    ) -> Result<Message<Path, Method, Self::AsOsc>, AddressErr> {
+        Ok(Message::new(
+            path.into_address(method)?,
+            self.iter()
+                .cloned()
+                .map(|e| {
+                    e.into_atomic()
+                        .map_err(|err| AddressErr::StringErr(err.into()))
+                })
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
 
 #[cfg(feature = "alloc")]
 impl IntoOsc for Data {
@@ -164,6 +280,27 @@ impl IntoOsc for Dynamic {
     }
 }
 
+/// Named parameters as alternating key and value arguments (e.g. `,sisi`), in sorted key order.
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl<V: Into<Data>> IntoOsc for alloc::collections::BTreeMap<&str, V> {
+    type AsOsc = Dynamic;
+    #[inline]
+    fn into_osc<Path: IntoAddress<Method>, Method: IntoIntoAddress>(
+        self,
+        path: Path,
+        method: Method,
+    ) -> Result<Message<Path, Method, Self::AsOsc>, AddressErr> {
+        let address = path.into_address(method)?;
+        let mut dynamic = Dynamic(alloc::vec::Vec::with_capacity(self.len().saturating_mul(2)));
+        for (key, value) in self {
+            dynamic.push_str(key).map_err(AddressErr::StringErr)?;
+            dynamic.push(value.into());
+        }
+        Ok(Message::new(address, dynamic))
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl IntoOsc for alloc::string::String {
     type AsOsc = (DynamicString,);
@@ -180,6 +317,21 @@ impl IntoOsc for alloc::string::String {
     }
 }
 
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl<'s> IntoOsc for &'s alloc::string::String {
+    type AsOsc = (String<'s>,);
+    /// Borrows the string instead of consuming it.
+    #[inline(always)]
+    fn into_osc<Path: IntoAddress<Method>, Method: IntoIntoAddress>(
+        self,
+        path: Path,
+        method: Method,
+    ) -> Result<Message<Path, Method, Self::AsOsc>, AddressErr> {
+        self.as_str().into_osc(path, method)
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[allow(unused_qualifications)]
 impl IntoOsc for alloc::vec::Vec<u8> {
@@ -192,9 +344,7 @@ impl IntoOsc for alloc::vec::Vec<u8> {
     ) -> Result<Message<Path, Method, Self::AsOsc>, AddressErr> {
         Ok(Message::new(
             path.into_address(method)?,
-            (self
-                .into_atomic()
-                .map_err(|e| AddressErr::StringErr(e.into()))?,),
+            (self.into_atomic().map_err(AddressErr::StringErr)?,),
         ))
     }
 }
@@ -23,7 +23,6 @@
 // https://doc.rust-lang.org/rustc/lints/listing/allowed-by-default.html
 #![warn(
     absolute_paths_not_starting_with_crate,
-    elided_lifetimes_in_paths,
     explicit_outlives_requirements,
     keyword_idents,
     let_underscore_drop,
@@ -32,10 +31,8 @@
     missing_abi,
     missing_copy_implementations,
     missing_debug_implementations,
-    missing_docs,
     non_ascii_idents,
     noop_method_call,
-    pointer_structural_match,
     rust_2021_incompatible_closure_captures,
     rust_2021_incompatible_or_patterns,
     rust_2021_prefixes_incompatible_syntax,
@@ -54,29 +51,41 @@
     unused_macro_rules,
     unused_qualifications,
     unused_results,
-    unused_tuple_struct_fields,
     variant_size_differences
 )]
 #![allow(
+    clippy::absolute_paths,
+    clippy::allow_attributes,
+    clippy::allow_attributes_without_reason,
+    clippy::arbitrary_source_item_ordering,
+    clippy::big_endian_bytes,
     clippy::blanket_clippy_restriction_lints,
+    clippy::cfg_not_test,
+    clippy::doc_paragraphs_missing_punctuation,
     clippy::expect_used,
+    clippy::field_scoped_visibility_modifiers,
     clippy::implicit_return,
+    clippy::infallible_try_from,
     clippy::inline_always,
     clippy::let_underscore_untyped,
     clippy::match_ref_pats,
+    clippy::min_ident_chars,
     clippy::missing_trait_methods,
     clippy::mod_module_files,
     clippy::multiple_unsafe_ops_per_block,
     clippy::needless_borrowed_reference,
     clippy::partial_pub_fields,
     clippy::pub_use,
+    clippy::pub_with_shorthand,
     clippy::question_mark_used,
     clippy::redundant_pub_crate,
     clippy::ref_patterns,
     clippy::semicolon_outside_block,
     clippy::separated_literal_suffix,
+    clippy::single_call_fn,
     clippy::single_char_lifetime_names,
     clippy::string_add,
+    clippy::unused_trait_names,
     clippy::use_self,
     clippy::wildcard_imports
 )]
@@ -84,34 +93,70 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod address;
 mod atomic;
 mod batch;
+mod borrowed;
 mod decode;
 mod into_osc;
+mod macros;
 mod message;
+mod queue;
 mod tag;
 mod tuple;
 
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+mod bundle;
 #[cfg(feature = "alloc")]
 mod dynamic;
+#[cfg(feature = "alloc")]
+mod packet;
+
+#[cfg(feature = "std")]
+pub mod net;
 
 pub use {
-    address::{valid_address_character, Address, AddressErr, IntoAddress, IntoIntoAddress},
-    atomic::{Atomic, Blob, Float, Integer, IntoAtomic, InvalidContents, String},
-    batch::{Batch, Batched},
-    decode::{Aligned4B, Decode, Misaligned4B},
+    address::{
+        valid_address_character, valid_address_character_with, Address, AddressCharPolicy,
+        AddressDecodeErr, AddressErr, IntoAddress, IntoIntoAddress,
+    },
+    atomic::{
+        decode_str_into, Atomic, Blob, BlobDecodeErr, Float, Integer, IntoAtomic, InvalidContents,
+        SizePrefixed, String, StringDecodeErr, TimeTag,
+    },
+    batch::{padded_len, padded_len_checked, Batch, Batched},
+    borrowed::{BorrowedArgs, BorrowedArgsIter, BorrowedData, Segments},
+    decode::{
+        skip_prefix, Aligned4B, CountingIter, Decode, DecodeConfig, DecodeErr, DynamicDecodeErr,
+        Endian, Misaligned4B, MissingTypeTags, NegativeBlobSize, OscVersion,
+    },
     into_osc::IntoOsc,
-    message::Message,
-    tag::{Tag, TagDecodeErr},
-    tuple::Tuple,
+    message::{Message, MessageDecodeErr, MessageIter},
+    queue::MessageQueue,
+    tag::{decode_tags_borrowed, Tag, TagDecodeErr, TagsIter},
+    tuple::{Tuple, TupleDecodeErr, TypeTags},
 };
 
+#[doc(hidden)]
+pub use macros::split_literal as __split_literal;
+
 #[cfg(feature = "alloc")]
 pub use {
-    address::AddressDecodeErr,
-    atomic::{DynamicBlob, DynamicString, StringDecodeErr},
-    dynamic::{Data, Dynamic, DynamicDecodeErr, Tags},
+    atomic::{BlobWriter, DynamicBlob, DynamicString},
+    builder::MessageBuilder,
+    bundle::{BundleDecodeErr, DynamicBundle},
+    decode::IncrementalDecoder,
+    dynamic::{
+        Data, DataIter, DataRefIter, DataVisitor, Dynamic, FromDynamic, SchemaMismatch, Tags,
+        UnknownArg, UnknownArgErr,
+    },
+    message::{MessageStream, RawMessage},
+    packet::{Packet, PacketDecodeErr, PacketIter},
 };
 
 #[cfg(test)]
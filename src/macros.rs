@@ -0,0 +1,44 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Shorthand for writing messages by hand.
+
+use crate::{AddressErr, Segments};
+
+/// Build a message from an address written the usual way, followed by its arguments.
+///
+/// Expands to the equivalent `into_osc` call, so it returns `Result<Message<..>, AddressErr>`:
+/// ```rust
+/// use osc::{osc, IntoOsc};
+/// let message = osc!("/synth/gain", 0.5_f32).unwrap();
+/// assert!(message.encoded_eq(&(0.5_f32,).into_osc(["synth"], "gain").unwrap()));
+/// ```
+#[macro_export]
+macro_rules! osc {
+    ($address:expr $(, $arg:expr)* $(,)?) => {
+        $crate::__split_literal($address)
+            .and_then(|(path, method)| $crate::IntoOsc::into_osc(($($arg,)*), path, method))
+    };
+}
+
+/// Split an address like `/a/b/c` into its path segments (`a`, `b`) and method (`c`).
+///
+/// Characters (and an empty method) are validated when the message is built,
+/// but empty path segments have to be caught here, since `Segments` skips them.
+/// # Errors
+/// If the address doesn't start with `/` or has an empty path segment (e.g. `/a//b`).
+#[doc(hidden)]
+#[inline]
+pub fn split_literal(address: &str) -> Result<(Segments<'_>, &str), AddressErr> {
+    let rest = address
+        .strip_prefix('/')
+        .ok_or(AddressErr::MissingLeadingSlash)?;
+    let (path, method) = rest.rsplit_once('/').unwrap_or(("", rest));
+    if !path.is_empty() && path.split('/').any(str::is_empty) {
+        return Err(AddressErr::Empty);
+    }
+    Ok((Segments::new(path), method))
+}
@@ -7,11 +7,14 @@
 //! Typed data to a specified address.
 
 use crate::{
-    address::{Address, IntoIntoAddress},
+    address::{Address, IntoAddress, IntoIntoAddress, PathRef, SegmentRef},
+    batch::padded_len,
     tuple::Tuple,
-    Batch, Batched, Tag,
+    AddressDecodeErr, AddressErr, DynamicDecodeErr,
 };
-use core::iter::{once, Chain, Once};
+
+#[cfg(feature = "alloc")]
+use crate::{Decode, DecodeConfig, Dynamic, Misaligned4B};
 
 /// Default type parameter for the path of a message.
 #[cfg(feature = "alloc")]
@@ -59,34 +62,538 @@ impl<Path: IntoIterator<Item = Method>, Method: IntoIntoAddress, Data: Tuple>
     pub const fn new(address: Address<Path, Method>, data: Data) -> Self {
         Self { address, data }
     }
+    /// Address to which this message is sent.
+    #[inline(always)]
+    pub const fn address(&self) -> &Address<Path, Method> {
+        &self.address
+    }
+    /// Arguments to this message.
+    #[inline(always)]
+    pub const fn data(&self) -> &Data {
+        &self.data
+    }
+    /// Split into the address and arguments, e.g. to route the arguments elsewhere.
+    #[inline(always)]
+    pub fn into_parts(self) -> (Address<Path, Method>, Data) {
+        (self.address, self.data)
+    }
+    /// Encode without consuming (or cloning) this message, e.g. to send it to many sockets.
+    #[inline]
+    pub fn encode_bytes<'m, Segment: IntoIntoAddress + 'm>(
+        &'m self,
+    ) -> impl Iterator<Item = u8> + 'm
+    where
+        &'m Path: IntoIterator<Item = Segment>,
+        &'m Method: IntoIntoAddress<IntoAddr = Segment::IntoAddr>,
+        &'m Data: Tuple,
+    {
+        self.by_ref().into_iter()
+    }
+    /// Borrow each part, so the result encodes without consuming (or cloning) this message.
+    #[inline(always)]
+    fn by_ref<'m, Segment: IntoIntoAddress>(
+        &'m self,
+    ) -> Message<PathRef<'m, Path, Segment, Method>, SegmentRef<Segment, &'m Method>, &'m Data>
+    where
+        &'m Path: IntoIterator<Item = Segment>,
+        &'m Method: IntoIntoAddress<IntoAddr = Segment::IntoAddr>,
+        &'m Data: Tuple,
+    {
+        Message::new(self.address.by_ref(), &self.data)
+    }
+    /// Encode into a vector allocated once at exactly `encoded_len()`, unlike `into_iter().collect()`.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    #[allow(unused_qualifications)]
+    pub fn to_vec<'m, Segment: IntoIntoAddress>(&'m self) -> alloc::vec::Vec<u8>
+    where
+        &'m Path: IntoIterator<Item = Segment>,
+        <&'m Path as IntoIterator>::IntoIter: Clone,
+        &'m Method: IntoIntoAddress<IntoAddr = Segment::IntoAddr>,
+        &'m Data: Tuple,
+    {
+        let by_ref = self.by_ref();
+        let mut v = alloc::vec::Vec::with_capacity(by_ref.encoded_len());
+        v.extend(by_ref);
+        v
+    }
+    /// Encoded bytes of this message, e.g. to use as a key when deduplicating by wire representation.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    #[allow(unused_qualifications)]
+    pub fn canonical_bytes<'m, Segment: IntoIntoAddress>(&'m self) -> alloc::vec::Vec<u8>
+    where
+        &'m Path: IntoIterator<Item = Segment>,
+        <&'m Path as IntoIterator>::IntoIter: Clone,
+        &'m Method: IntoIntoAddress<IntoAddr = Segment::IntoAddr>,
+        &'m Data: Tuple,
+    {
+        self.to_vec()
+    }
+    /// Write a human-readable dump of the encoded bytes, 16 per line, with hex on the left and ASCII on the right.
+    ///
+    /// Null bytes (terminators and padding) show up as `.` and other unprintable bytes as `?`.
+    /// # Errors
+    /// If the writer fails.
+    #[inline]
+    pub fn dump_to<'m, Segment: IntoIntoAddress, W: core::fmt::Write>(
+        &'m self,
+        w: &mut W,
+    ) -> core::fmt::Result
+    where
+        &'m Path: IntoIterator<Item = Segment>,
+        &'m Method: IntoIntoAddress<IntoAddr = Segment::IntoAddr>,
+        &'m Data: Tuple,
+    {
+        let mut iter = self.by_ref().into_iter().peekable();
+        while iter.peek().is_some() {
+            let mut line = [0; 16];
+            let mut len = 0_usize;
+            for (slot, byte) in line.iter_mut().zip(iter.by_ref().take(16)) {
+                *slot = byte;
+                len = len.saturating_add(1);
+            }
+            for (i, &byte) in line.iter().enumerate() {
+                if i != 0 {
+                    w.write_char(' ')?;
+                }
+                if i < len {
+                    write!(w, "{byte:02x}")?;
+                } else {
+                    w.write_str("  ")?;
+                }
+            }
+            w.write_str(" | ")?;
+            for &byte in line.iter().take(len) {
+                w.write_char(match byte {
+                    0 => '.',
+                    b' '..=b'~' => char::from(byte),
+                    _ => '?',
+                })?;
+            }
+            w.write_char('\n')?;
+        }
+        Ok(())
+    }
+    /// Human-readable dump of the encoded bytes; see `dump_to`.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    #[allow(unused_qualifications)]
+    pub fn hexdump<'m, Segment: IntoIntoAddress>(&'m self) -> alloc::string::String
+    where
+        &'m Path: IntoIterator<Item = Segment>,
+        &'m Method: IntoIntoAddress<IntoAddr = Segment::IntoAddr>,
+        &'m Data: Tuple,
+    {
+        let mut s = alloc::string::String::new();
+        // Writing to a `String` never fails.
+        self.dump_to(&mut s).unwrap_or_default();
+        s
+    }
+    /// Whether two messages encode to exactly the same bytes, regardless of how each is represented.
+    #[inline]
+    #[must_use]
+    pub fn encoded_eq<
+        'm,
+        Segment: IntoIntoAddress,
+        OtherSegment: IntoIntoAddress,
+        OtherPath: IntoIterator<Item = OtherMethod>,
+        OtherMethod: IntoIntoAddress,
+        OtherData: Tuple,
+    >(
+        &'m self,
+        other: &'m Message<OtherPath, OtherMethod, OtherData>,
+    ) -> bool
+    where
+        &'m Path: IntoIterator<Item = Segment>,
+        &'m Method: IntoIntoAddress<IntoAddr = Segment::IntoAddr>,
+        &'m Data: Tuple,
+        &'m OtherPath: IntoIterator<Item = OtherSegment>,
+        &'m OtherMethod: IntoIntoAddress<IntoAddr = OtherSegment::IntoAddr>,
+        &'m OtherData: Tuple,
+    {
+        self.by_ref().into_iter().eq(other.by_ref())
+    }
+    /// Compare encoded bytes lexicographically, e.g. to sort messages into a reproducible wire order.
+    ///
+    /// Unlike the derived `Ord`, which compares structurally, this agrees with comparing `canonical_bytes`.
+    #[inline]
+    #[must_use]
+    pub fn cmp_encoded<
+        'm,
+        Segment: IntoIntoAddress,
+        OtherSegment: IntoIntoAddress,
+        OtherPath: IntoIterator<Item = OtherMethod>,
+        OtherMethod: IntoIntoAddress,
+        OtherData: Tuple,
+    >(
+        &'m self,
+        other: &'m Message<OtherPath, OtherMethod, OtherData>,
+    ) -> core::cmp::Ordering
+    where
+        &'m Path: IntoIterator<Item = Segment>,
+        &'m Method: IntoIntoAddress<IntoAddr = Segment::IntoAddr>,
+        &'m Data: Tuple,
+        &'m OtherPath: IntoIterator<Item = OtherSegment>,
+        &'m OtherMethod: IntoIntoAddress<IntoAddr = OtherSegment::IntoAddr>,
+        &'m OtherData: Tuple,
+    {
+        self.by_ref().into_iter().cmp(other.by_ref())
+    }
+}
+
+impl<Path: IntoAddress<Method>, Method: IntoIntoAddress> Message<Path, Method, ()> {
+    /// Message with no arguments, e.g. a notification like `/ping`.
+    /// # Errors
+    /// If the address is invalid (according to the OSC spec).
+    #[inline]
+    pub fn empty(path: Path, method: Method) -> Result<Self, AddressErr> {
+        Ok(Self::new(path.into_address(method)?, ()))
+    }
+}
+
+impl<Path: IntoIterator<Item = Method> + Clone, Method: IntoIntoAddress, Data: Tuple>
+    Message<Path, Method, Data>
+{
+    /// Exact number of bytes this message will encode to.
+    ///
+    /// Doesn't encode anything unless the arguments can't say how long they are (see `Tuple::byte_len`),
+    /// in which case only a copy of the arguments is encoded.
+    #[inline]
+    #[must_use]
+    pub fn encoded_len(&self) -> usize
+    where
+        Data: Clone,
+    {
+        // Comma, one character per type, and a null terminator.
+        let type_tag = padded_len(self.data.type_tag().count().saturating_add(2));
+        self.address
+            .encoded_len()
+            .saturating_add(type_tag)
+            .saturating_add(
+                self.data
+                    .byte_len()
+                    .unwrap_or_else(|| self.data.clone().chain().count()),
+            )
+    }
+    /// Same address with different arguments, e.g. to reply to a request.
+    #[inline]
+    #[must_use]
+    pub fn reply_with<D: Tuple>(&self, data: D) -> Message<Path, Method, D> {
+        Message::new(self.address.clone(), data)
+    }
+}
+
+/// Type tag and argument bytes of a message, reading each tag lazily out of the arguments it describes.
+enum Rest<Data: Tuple> {
+    /// Type tag string, including the comma, null terminator, and padding.
+    Tags {
+        /// Arguments, held until every tag has been read out of them.
+        data: Option<Data>,
+        /// Number of type tag bytes already yielded.
+        written: usize,
+        /// Total number of type tag bytes.
+        len: usize,
+    },
+    /// Arguments, once the type tags are done.
+    Args(Data::Chained),
+}
+
+/// Iterator over the bytes of an encoded message.
+///
+/// If the arguments know their length (see `Tuple::byte_len`), so does this iterator,
+/// and `count` only walks whatever's left of the address.
+/// `last` skips the address entirely, since every message ends in its type tags or arguments.
+#[allow(clippy::module_name_repetitions, missing_debug_implementations)]
+pub struct MessageIter<Path: IntoIterator<Item = Method>, Method: IntoIntoAddress, Data: Tuple> {
+    /// Address bytes not yet yielded.
+    address: core::iter::Fuse<<Address<Path, Method> as IntoIterator>::IntoIter>,
+    /// Type tag and argument bytes not yet yielded.
+    rest: Rest<Data>,
+    /// Number of bytes left in `rest`, if the arguments know how long they are.
+    rest_len: Option<usize>,
+}
+
+impl<Path: IntoIterator<Item = Method>, Method: IntoIntoAddress, Data: Tuple> Iterator
+    for MessageIter<Path, Method, Data>
+{
+    type Item = u8;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let some @ Some(_) = self.address.next() {
+            return some;
+        }
+        let byte = loop {
+            match self.rest {
+                Rest::Tags {
+                    ref mut data,
+                    ref mut written,
+                    len,
+                } => {
+                    if *written < len {
+                        let byte = match written.checked_sub(1) {
+                            None => b',',
+                            // Null terminator and padding once the tags run out.
+                            Some(index) => data.as_ref()?.tag_at(index).unwrap_or(0),
+                        };
+                        *written = written.saturating_add(1);
+                        break byte;
+                    }
+                    let args = data.take()?.chain();
+                    self.rest = Rest::Args(args);
+                }
+                Rest::Args(ref mut args) => break args.next()?,
+            }
+        };
+        self.rest_len = self.rest_len.map(|len| len.saturating_sub(1));
+        Some(byte)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.address.size_hint();
+        self.rest_len.map_or((lo, None), |rest_len| {
+            (
+                lo.saturating_add(rest_len),
+                hi.and_then(|n| n.checked_add(rest_len)),
+            )
+        })
+    }
+    #[inline]
+    fn count(self) -> usize {
+        match self.rest_len {
+            Some(rest_len) => self.address.count().saturating_add(rest_len),
+            None => self.fold(0, |n, _| n.saturating_add(1)),
+        }
+    }
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        match self.rest {
+            Rest::Tags { data, written, len } => {
+                // Type tags always end in a null terminator or padding.
+                let tags_end = (written < len).then_some(0);
+                data?.chain().last().or(tags_end)
+            }
+            Rest::Args(args) => args.last(),
+        }
+    }
+}
+
+/// The type tags are dropped once they run out, so only the arguments need to be fused.
+impl<Path: IntoIterator<Item = Method>, Method: IntoIntoAddress, Data: Tuple>
+    core::iter::FusedIterator for MessageIter<Path, Method, Data>
+where
+    Data::Chained: core::iter::FusedIterator,
+{
 }
 
 impl<Path: IntoIterator<Item = Method>, Method: IntoIntoAddress, Data: Tuple> IntoIterator
     for Message<Path, Method, Data>
 {
     type Item = u8;
-    type IntoIter = Chain<
-        Chain<
-            <Address<Path, Method> as IntoIterator>::IntoIter,
-            Batched<
-                Chain<Chain<Once<u8>, core::iter::Map<Data::TypeTagIter, fn(Tag) -> u8>>, Once<u8>>,
-            >,
-        >,
-        Data::Chained,
-    >;
-    #[inline]
-    #[allow(clippy::as_conversions, clippy::as_underscore, trivial_casts)]
+    type IntoIter = MessageIter<Path, Method, Data>;
+    #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        self.address
-            .into_iter()
+        // Comma, one character per type, and a null terminator.
+        let tags_len = padded_len(self.data.type_tag().count().saturating_add(2));
+        MessageIter {
             // batched already
-            .chain(
-                once(b',')
-                    .chain(self.data.type_tag().map((|tag| tag as _) as fn(Tag) -> u8))
-                    .chain(once(b'\0'))
-                    .batch(),
-            )
-            .chain(self.data.chain())
+            address: self.address.into_iter().fuse(),
+            rest_len: self.data.byte_len().map(|len| len.saturating_add(tags_len)),
+            rest: Rest::Tags {
+                data: Some(self.data),
+                written: 0,
+                len: tags_len,
+            },
+        }
+    }
+}
+
+/// Any possible error while decoding an OSC message.
+#[non_exhaustive]
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum MessageDecodeErr {
+    /// Error parsing the address.
+    AddressErr(AddressDecodeErr),
+    /// Error parsing the type tags or data.
+    DataErr(DynamicDecodeErr),
+}
+
+impl core::fmt::Display for MessageDecodeErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            &Self::AddressErr(e) => write!(f, "{e}"),
+            &Self::DataErr(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<AddressDecodeErr> for MessageDecodeErr {
+    #[inline]
+    fn from(value: AddressDecodeErr) -> Self {
+        Self::AddressErr(value)
+    }
+}
+
+impl From<DynamicDecodeErr> for MessageDecodeErr {
+    #[inline]
+    fn from(value: DynamicDecodeErr) -> Self {
+        Self::DataErr(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Message {
+    /// Flatten into the full address (e.g. `/a/b/c`) and arguments, e.g. for scripting or logging.
+    #[inline]
+    #[must_use]
+    #[allow(unused_qualifications)]
+    pub fn into_flat(self) -> (alloc::string::String, Dynamic) {
+        (alloc::string::ToString::to_string(&self.address), self.data)
+    }
+    /// Decode, reading the address and arguments as `config` says; see `Dynamic::decode_with_config`.
+    /// # Errors
+    /// If the address, type tags, or any argument is invalid, or if the iterator ends early.
+    #[inline]
+    pub fn decode_with_config<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+    ) -> Result<Self, Misaligned4B<MessageDecodeErr>> {
+        Ok(Self {
+            address: Address::decode_with_config(iter, config).map_err(|e| e.map(Into::into))?,
+            data: Dynamic::decode_with_config(iter, config).map_err(|e| e.map(Into::into))?,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Decode for Message {
+    type Error = MessageDecodeErr;
+    #[inline]
+    fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
+        Self::decode_with_config(iter, DecodeConfig::default())
+    }
+}
+
+/// Lazily decode a stream of back-to-back OSC messages (e.g. several packets in one UDP payload).
+///
+/// Yields `None` once the stream ends cleanly between two messages;
+/// `Some(Err(Misaligned4B::End))` means the stream ended partway through a message.
+/// After any error, the position in the stream is meaningless, so every later call yields `None`.
+#[cfg(feature = "alloc")]
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct MessageStream<I: Iterator<Item = u8>> {
+    /// Iterator over raw bytes, peekable to distinguish a clean end from a truncated message.
+    iter: core::iter::Peekable<I>,
+    /// Whether we've already returned an error.
+    poisoned: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator<Item = u8>> MessageStream<I> {
+    /// Lazily decode a stream of back-to-back OSC messages.
+    #[inline]
+    pub fn new<II: IntoIterator<IntoIter = I>>(iter: II) -> Self {
+        Self {
+            iter: iter.into_iter().peekable(),
+            poisoned: false,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator<Item = u8>> Iterator for MessageStream<I> {
+    type Item = Result<Message, Misaligned4B<MessageDecodeErr>>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.poisoned {
+            return None;
+        }
+        let _: &u8 = self.iter.peek()?;
+        let result = Message::decode(&mut self.iter);
+        self.poisoned = result.is_err();
+        Some(result)
+    }
+}
+
+/// Encoded bytes of a message, kept as-is to forward later without decoding its arguments (e.g. in a proxy).
+///
+/// Decoding reads exactly one message, so several concatenated in a stream come out one at a time.
+#[cfg(feature = "alloc")]
+#[allow(clippy::module_name_repetitions, unused_qualifications)]
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct RawMessage(pub(crate) alloc::vec::Vec<u8>);
+
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl RawMessage {
+    /// Wrap already-encoded bytes, trusting that they're a whole message.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(bytes: alloc::vec::Vec<u8>) -> Self {
+        Self(bytes)
+    }
+    /// Borrow the encoded bytes.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl From<RawMessage> for alloc::vec::Vec<u8> {
+    #[inline(always)]
+    fn from(value: RawMessage) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl IntoIterator for RawMessage {
+    type Item = u8;
+    type IntoIter = alloc::vec::IntoIter<u8>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Decode for RawMessage {
+    type Error = MessageDecodeErr;
+    /// Read exactly one message's bytes, finding where it ends from its address and type tags.
+    ///
+    /// The address and type tags are checked; arguments are only read far enough to find where each ends
+    /// (see `Tags::skip_arguments`), so e.g. a non-ASCII string passes through untouched.
+    #[inline]
+    #[allow(unused_qualifications)]
+    fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
+        let mut v = alloc::vec::Vec::new();
+        let mut recording = iter.by_ref().inspect(|&byte| v.push(byte));
+        let _address: Address<AddrDefault, MethodDefault> =
+            Address::decode(&mut recording).map_err(|e| e.map(Into::into))?;
+        let tags = crate::Tags::decode(&mut recording)
+            .map_err(|e| e.map(|err| DynamicDecodeErr::TypeTagErr(err).into()))?;
+        let _: usize = tags
+            .skip_arguments(&mut recording)
+            .map_err(|e| e.map(Into::into))?;
+        Ok(Self(v))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Message {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Message::new(u.arbitrary()?, u.arbitrary()?))
     }
 }
 
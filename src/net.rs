@@ -0,0 +1,107 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Send and receive OSC messages over UDP.
+
+use crate::{Decode, Message};
+use alloc::string::ToString;
+use std::{
+    io,
+    net::{ToSocketAddrs, UdpSocket},
+};
+
+/// Largest possible payload of a single UDP datagram over IPv4.
+pub const MAX_UDP_PAYLOAD: usize = 65507;
+
+/// Encode a message and send it as a single UDP datagram.
+/// # Errors
+/// If the socket fails to send.
+#[inline]
+pub fn send<A: ToSocketAddrs, M: IntoIterator<Item = u8>>(
+    socket: &UdpSocket,
+    addr: A,
+    message: M,
+) -> io::Result<usize> {
+    socket.send_to(&encode(message), addr)
+}
+
+/// Block until a UDP datagram arrives in `buffer`, then decode it as an OSC message.
+///
+/// Reuse one buffer across calls. Anything past its end is cut off,
+/// so it should hold `MAX_UDP_PAYLOAD` bytes unless you know how large each datagram can be.
+/// # Errors
+/// If the socket fails to receive or if the datagram is not exactly one valid OSC message.
+#[inline]
+pub fn recv(socket: &UdpSocket, buffer: &mut [u8]) -> io::Result<Message> {
+    let size = socket.recv(buffer)?;
+    decode(buffer, size)
+}
+
+/// Collect an encoded message into one contiguous datagram.
+#[inline]
+#[allow(unused_qualifications)]
+fn encode<M: IntoIterator<Item = u8>>(message: M) -> alloc::vec::Vec<u8> {
+    message.into_iter().collect()
+}
+
+/// Decode the first `size` bytes of a receive buffer as exactly one OSC message.
+#[inline]
+fn decode(buffer: &[u8], size: usize) -> io::Result<Message> {
+    Message::decode_slice(buffer.get(..size).unwrap_or(buffer))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+/// UDP socket that sends and receives OSC messages without blocking an async runtime.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct AsyncSocket(tokio::net::UdpSocket);
+
+#[cfg(feature = "tokio")]
+impl AsyncSocket {
+    /// Bind a new socket to this local address.
+    /// # Errors
+    /// If the socket can't be bound.
+    #[inline]
+    pub async fn bind<A: tokio::net::ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        tokio::net::UdpSocket::bind(addr).await.map(Self)
+    }
+    /// Local address this socket is bound to.
+    /// # Errors
+    /// If the underlying socket can't report its address.
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<core::net::SocketAddr> {
+        self.0.local_addr()
+    }
+    /// Encode a message and send it as a single UDP datagram.
+    /// # Errors
+    /// If the socket fails to send.
+    #[inline]
+    pub async fn send<A: tokio::net::ToSocketAddrs, M: IntoIterator<Item = u8>>(
+        &self,
+        addr: A,
+        message: M,
+    ) -> io::Result<usize> {
+        self.0.send_to(&encode(message), addr).await
+    }
+    /// Wait until a UDP datagram arrives in `buffer`, then decode it as an OSC message.
+    ///
+    /// Reuse one buffer across calls, sized as for the blocking `recv`.
+    /// # Errors
+    /// If the socket fails to receive or if the datagram is not exactly one valid OSC message.
+    #[inline]
+    pub async fn recv(&self, buffer: &mut [u8]) -> io::Result<Message> {
+        let size = self.0.recv(buffer).await?;
+        decode(buffer, size)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl From<tokio::net::UdpSocket> for AsyncSocket {
+    #[inline(always)]
+    fn from(value: tokio::net::UdpSocket) -> Self {
+        Self(value)
+    }
+}
@@ -0,0 +1,182 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Anything sent over the wire: either a single message or a bundle.
+
+use crate::{
+    Aligned4B, BundleDecodeErr, Decode, DecodeConfig, DynamicBundle, Message, MessageDecodeErr,
+    Misaligned4B,
+};
+
+/// Anything sent over the wire: either a single message or a bundle.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Packet {
+    /// Single message, starting with `/`.
+    Message(Message),
+    /// Bundle of messages and/or other bundles, starting with `#bundle`.
+    Bundle(DynamicBundle),
+}
+
+impl Packet {
+    /// Exact number of bytes this packet will encode to, without encoding it.
+    #[inline]
+    #[must_use]
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            &Self::Message(ref message) => message.encoded_len(),
+            &Self::Bundle(ref bundle) => bundle.encoded_len(),
+        }
+    }
+    /// Compare encoded bytes lexicographically, without encoding either packet.
+    #[inline]
+    pub(crate) fn cmp_encoded(&self, other: &Self) -> core::cmp::Ordering {
+        match (self, other) {
+            (&Self::Message(ref a), &Self::Message(ref b)) => a.cmp_encoded(b),
+            (&Self::Bundle(ref a), &Self::Bundle(ref b)) => a.cmp_encoded(b),
+            // A bundle's `#` sorts before a message's `/`.
+            (&Self::Bundle(_), &Self::Message(_)) => core::cmp::Ordering::Less,
+            (&Self::Message(_), &Self::Bundle(_)) => core::cmp::Ordering::Greater,
+        }
+    }
+    /// Decode, limiting how deeply bundles nest and reading arguments as `config` says.
+    /// # Errors
+    /// If the packet is invalid, nests bundles deeper than `config.max_depth`, or the iterator ends early.
+    #[inline]
+    pub fn decode_with_config<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+    ) -> Result<Self, Misaligned4B<PacketDecodeErr>> {
+        Self::decode_at_depth(iter, config, 1)
+    }
+    /// Decode a packet that, if it's a bundle, would be nested `depth` deep (counting a top-level bundle as 1).
+    #[inline]
+    pub(crate) fn decode_at_depth<I: Iterator<Item = u8>>(
+        iter: &mut I,
+        config: DecodeConfig,
+        depth: usize,
+    ) -> Result<Self, Misaligned4B<PacketDecodeErr>> {
+        let chunk = Aligned4B::<PacketDecodeErr>::decode(iter)?;
+        let start = chunk.0;
+        let mut rest = chunk.into_iter().chain(iter);
+        match start {
+            b'/' => Message::decode_with_config(&mut rest, config)
+                .map(Self::Message)
+                .map_err(|e| e.map(Into::into)),
+            b'#' => DynamicBundle::decode_at_depth(&mut rest, config, depth)
+                .map(Self::Bundle)
+                .map_err(|e| e.map(Into::into)),
+            _ => Err(Misaligned4B::Other(PacketDecodeErr::UnrecognizedStart(
+                start,
+            ))),
+        }
+    }
+}
+
+impl From<Message> for Packet {
+    #[inline(always)]
+    fn from(value: Message) -> Self {
+        Self::Message(value)
+    }
+}
+
+impl From<DynamicBundle> for Packet {
+    #[inline(always)]
+    fn from(value: DynamicBundle) -> Self {
+        Self::Bundle(value)
+    }
+}
+
+/// Iterator over the bytes of an encoded packet.
+#[non_exhaustive]
+#[allow(
+    clippy::large_enum_variant,
+    clippy::module_name_repetitions,
+    missing_debug_implementations
+)]
+pub enum PacketIter {
+    /// Bytes of a single message.
+    Message(<Message as IntoIterator>::IntoIter),
+    /// Bytes of a bundle.
+    #[allow(unused_qualifications)]
+    Bundle(alloc::vec::IntoIter<u8>),
+}
+
+impl Iterator for PacketIter {
+    type Item = u8;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            &mut Self::Message(ref mut iter) => iter.next(),
+            &mut Self::Bundle(ref mut iter) => iter.next(),
+        }
+    }
+}
+
+impl core::iter::FusedIterator for PacketIter {}
+
+impl IntoIterator for Packet {
+    type Item = u8;
+    type IntoIter = PacketIter;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Self::Message(message) => PacketIter::Message(message.into_iter()),
+            Self::Bundle(bundle) => PacketIter::Bundle(bundle.into_iter()),
+        }
+    }
+}
+
+/// Any possible error while decoding an OSC packet.
+#[non_exhaustive]
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum PacketDecodeErr {
+    /// Started with neither `/` (a message) nor `#` (a bundle).
+    UnrecognizedStart(u8),
+    /// Error parsing a message.
+    MessageErr(MessageDecodeErr),
+    /// Error parsing a bundle.
+    BundleErr(BundleDecodeErr),
+}
+
+impl core::fmt::Display for PacketDecodeErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            &Self::UnrecognizedStart(c) => write!(
+                f,
+                "OSC packet started with '{}' instead of '/' or '#'.",
+                core::ascii::escape_default(c)
+            ),
+            &Self::MessageErr(e) => write!(f, "{e}"),
+            &Self::BundleErr(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<MessageDecodeErr> for PacketDecodeErr {
+    #[inline]
+    fn from(value: MessageDecodeErr) -> Self {
+        Self::MessageErr(value)
+    }
+}
+
+impl From<BundleDecodeErr> for PacketDecodeErr {
+    #[inline]
+    fn from(value: BundleDecodeErr) -> Self {
+        Self::BundleErr(value)
+    }
+}
+
+impl Decode for Packet {
+    type Error = PacketDecodeErr;
+    /// Look at the first byte to decide between a message and a bundle.
+    #[inline]
+    fn decode<I: Iterator<Item = u8>>(iter: &mut I) -> Result<Self, Misaligned4B<Self::Error>> {
+        Self::decode_with_config(iter, DecodeConfig::default())
+    }
+}
@@ -0,0 +1,107 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Fixed-capacity queue of borrowed messages, for receivers without `alloc`.
+
+use crate::{BorrowedArgs, Message, Segments};
+
+/// Message borrowed from the buffer it was decoded from; see `Message::decode_borrowed`.
+type Borrowed<'a> = Message<Segments<'a>, &'a str, BorrowedArgs<'a>>;
+
+/// First-in, first-out ring buffer of up to `N` borrowed messages, without `alloc`.
+///
+/// Messages point into the buffers they were decoded from, so those buffers must outlive the queue,
+/// e.g. a handful of static receive buffers on a microcontroller.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct MessageQueue<'a, const N: usize> {
+    /// Slots, of which `len` starting at `head` (wrapping around) are occupied.
+    slots: [Option<Borrowed<'a>>; N],
+    /// Index of the oldest message.
+    head: usize,
+    /// Number of messages waiting.
+    len: usize,
+}
+
+impl<'a, const N: usize> MessageQueue<'a, N> {
+    /// Empty queue.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            slots: core::array::from_fn(|_| None),
+            head: 0,
+            len: 0,
+        }
+    }
+    /// Maximum number of messages this queue can hold at once.
+    #[inline(always)]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+    /// Number of messages waiting.
+    #[inline(always)]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Whether no messages are waiting.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Whether another `push` would fail.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.len >= N
+    }
+    /// Add a message to the back.
+    /// # Errors
+    /// If the queue is full, handing back the message that didn't fit.
+    #[inline]
+    pub fn push(&mut self, message: Borrowed<'a>) -> Result<(), Borrowed<'a>> {
+        if self.is_full() {
+            return Err(message);
+        }
+        let Some(slot) = self
+            .slots
+            .get_mut(wrap::<N>(self.head.saturating_add(self.len)))
+        else {
+            return Err(message);
+        };
+        *slot = Some(message);
+        self.len = self.len.saturating_add(1);
+        Ok(())
+    }
+    /// Remove the oldest message, if any.
+    #[inline]
+    pub fn pop(&mut self) -> Option<Borrowed<'a>> {
+        let message = self.slots.get_mut(self.head)?.take()?;
+        self.head = wrap::<N>(self.head.saturating_add(1));
+        self.len = self.len.saturating_sub(1);
+        Some(message)
+    }
+}
+
+impl<const N: usize> Default for MessageQueue<'_, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wrap an index that's gone at most one lap past the end back into `0..N`, without dividing.
+#[inline(always)]
+const fn wrap<const N: usize>(i: usize) -> usize {
+    if i >= N {
+        i.saturating_sub(N)
+    } else {
+        i
+    }
+}
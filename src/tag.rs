@@ -6,6 +6,8 @@
 
 //! Single-character OSC type tags.
 
+use crate::OscVersion;
+
 /// Any possible error in decoding an unknown number of OSC type tags.
 #[non_exhaustive]
 #[allow(clippy::module_name_repetitions)]
@@ -17,6 +19,12 @@ pub enum TagDecodeErr {
     MissingComma(u8),
     /// Returned a null terminator then the rest of the 4-byte chunk was not null.
     NullThenNonNull,
+    /// Array brackets parse as tags, but arguments inside arrays can't be decoded yet.
+    ArraysNotYetImplemented(u8),
+    /// More type tags than the limit passed to `Tags::decode_with_limit`.
+    TooMany,
+    /// Recognized type tag character, but not standard in the OSC version being decoded.
+    NotInVersion(u8),
 }
 
 impl core::fmt::Display for TagDecodeErr {
@@ -38,6 +46,17 @@ impl core::fmt::Display for TagDecodeErr {
                 "OSC address returned a null terminator, \
                 but then the rest of its 4-byte chunk was non-null."
             ),
+            &Self::ArraysNotYetImplemented(c) => write!(
+                f,
+                "OSC array arguments not yet implemented (required for the type tag '{}').",
+                core::ascii::escape_default(c)
+            ),
+            &Self::TooMany => write!(f, "OSC type tag string exceeds the maximum number of tags."),
+            &Self::NotInVersion(c) => write!(
+                f,
+                "Type tag '{}' isn't standard in the requested OSC version",
+                core::ascii::escape_default(c)
+            ),
         }
     }
 }
@@ -55,6 +74,86 @@ pub enum Tag {
     String = b's',
     /// Arbitrary known-length collection of bytes.
     Blob = b'b',
+    /// Explicitly no value, with no bytes of data (nonstandard in OSC 1.0).
+    #[cfg(feature = "nonstandard-types")]
+    Nil = b'N',
+    /// Start of an array, whose elements follow until the matching `ArrayClose` (nonstandard).
+    #[cfg(feature = "nonstandard-types")]
+    ArrayOpen = b'[',
+    /// End of an array (nonstandard).
+    #[cfg(feature = "nonstandard-types")]
+    ArrayClose = b']',
+}
+
+impl Tag {
+    /// The byte identifying this type in a type tag string, e.g. `b'i'` for `Integer`.
+    #[inline(always)]
+    #[must_use]
+    #[allow(clippy::as_conversions)]
+    pub const fn as_byte(self) -> u8 {
+        self as u8
+    }
+    /// The character identifying this type in a type tag string, e.g. `'i'` for `Integer`.
+    #[inline(always)]
+    #[must_use]
+    #[allow(clippy::as_conversions)]
+    pub const fn as_char(self) -> char {
+        self.as_byte() as char
+    }
+    /// Number of bytes this type's data occupies in a message, if known without reading it.
+    ///
+    /// `None` for variable-length types (strings and blobs), whose size depends on their contents.
+    #[inline]
+    #[must_use]
+    pub const fn payload_size(&self) -> Option<usize> {
+        match *self {
+            Self::Integer | Self::Float => Some(4),
+            Self::String | Self::Blob => None,
+            #[cfg(feature = "nonstandard-types")]
+            Self::Nil | Self::ArrayOpen | Self::ArrayClose => Some(0),
+        }
+    }
+    /// Whether this type is standard in the given version of the OSC spec.
+    #[inline]
+    #[must_use]
+    #[cfg_attr(not(feature = "nonstandard-types"), allow(unused_variables))]
+    pub const fn is_standard_in(self, version: OscVersion) -> bool {
+        match self {
+            Self::Integer | Self::Float | Self::String | Self::Blob => true,
+            #[cfg(feature = "nonstandard-types")]
+            Self::Nil => matches!(version, OscVersion::V1_1),
+            #[cfg(feature = "nonstandard-types")]
+            Self::ArrayOpen | Self::ArrayClose => false,
+        }
+    }
+}
+
+impl core::fmt::Display for Tag {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+impl From<Tag> for u8 {
+    #[inline(always)]
+    fn from(value: Tag) -> Self {
+        value.as_byte()
+    }
+}
+
+impl PartialEq<u8> for Tag {
+    #[inline(always)]
+    fn eq(&self, other: &u8) -> bool {
+        self.as_byte() == *other
+    }
+}
+
+impl PartialEq<Tag> for u8 {
+    #[inline(always)]
+    fn eq(&self, other: &Tag) -> bool {
+        *self == other.as_byte()
+    }
 }
 
 impl TryFrom<u8> for Tag {
@@ -66,11 +165,83 @@ impl TryFrom<u8> for Tag {
             b'f' => Self::Float,
             b's' => Self::String,
             b'b' => Self::Blob,
+            #[cfg(feature = "nonstandard-types")]
+            b'N' => Self::Nil,
+            #[cfg(feature = "nonstandard-types")]
+            b'[' => Self::ArrayOpen,
+            #[cfg(feature = "nonstandard-types")]
+            b']' => Self::ArrayClose,
             _ => return Err(TagDecodeErr::UnrecognizedTypeTag(value)),
         })
     }
 }
 
+/// Read type tags one at a time from a borrowed type tag string, e.g. `b",ifsb\0"`, without allocating.
+///
+/// Stops at the first null byte (or the end of the slice), so padding can be included or not.
+/// A missing comma or unrecognized character is yielded as an error, after which iteration stops.
+#[inline(always)]
+#[must_use]
+pub const fn decode_tags_borrowed(bytes: &[u8]) -> TagsIter<'_> {
+    TagsIter {
+        bytes,
+        started: false,
+    }
+}
+
+/// Type tags read lazily from a borrowed type tag string; see `decode_tags_borrowed`.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct TagsIter<'a> {
+    /// Bytes not yet read.
+    bytes: &'a [u8],
+    /// Whether we've already checked for the leading comma.
+    started: bool,
+}
+
+impl Iterator for TagsIter<'_> {
+    type Item = Result<Tag, TagDecodeErr>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&byte, rest) = self.bytes.split_first()?;
+        self.bytes = rest;
+        if !self.started {
+            self.started = true;
+            if byte != b',' {
+                self.bytes = &[];
+                return Some(Err(TagDecodeErr::MissingComma(byte)));
+            }
+            return self.next();
+        }
+        if byte == b'\0' {
+            self.bytes = &[];
+            return None;
+        }
+        let tag = Tag::try_from(byte);
+        if tag.is_err() {
+            self.bytes = &[];
+        }
+        Some(tag)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+/// Only tags that stand for a value on their own, so not array brackets.
+impl<'a> arbitrary::Arbitrary<'a> for Tag {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.choose(&[
+            Self::Integer,
+            Self::Float,
+            Self::String,
+            Self::Blob,
+            #[cfg(feature = "nonstandard-types")]
+            Self::Nil,
+        ])
+        .copied()
+    }
+}
+
 #[cfg(feature = "quickcheck")]
 impl quickcheck::Arbitrary for Tag {
     #[inline]
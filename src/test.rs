@@ -15,7 +15,7 @@ use crate::{IntoAtomic, IntoOsc, Tuple};
 
 /// Examples from <https://opensoundcontrol.stanford.edu/spec-1_0-examples.html>.
 mod from_the_spec {
-    use crate::AddressErr;
+    use crate::{AddressErr, Tag};
 
     use super::*;
 
@@ -35,11 +35,10 @@ mod from_the_spec {
 
     #[test]
     fn type_tag_f() {
-        assert!(().type_tag().eq(core::iter::empty()));
+        assert!(().type_tag().eq(core::iter::empty::<Tag>()));
     }
 
     #[test]
-    #[allow(clippy::as_conversions)]
     fn type_tag_iisfff() {
         assert!((
             0.into_atomic().unwrap(),
@@ -50,21 +49,15 @@ mod from_the_spec {
             0.0.into_atomic().unwrap(),
         )
             .type_tag()
-            .map(|tag| tag as u8)
             .eq("iisfff".bytes()));
     }
 
     #[test]
-    #[allow(clippy::as_conversions)]
     fn type_tag_none() {
-        assert!((0.0.into_atomic().unwrap(),)
-            .type_tag()
-            .map(|tag| tag as u8)
-            .eq("f".bytes()));
+        assert!((0.0.into_atomic().unwrap(),).type_tag().eq("f".bytes()));
     }
 
     #[test]
-    #[allow(clippy::as_conversions)]
     fn type_tag_ibb() {
         assert!((
             0.into_atomic().unwrap(),
@@ -72,7 +65,6 @@ mod from_the_spec {
             (&[]).into_atomic().unwrap()
         )
             .type_tag()
-            .map(|tag| tag as u8)
             .eq("ibb".bytes()));
     }
 
@@ -100,6 +92,8 @@ mod from_the_spec {
             \x3F\x9D\xF3\xB6\
             \x40\xB5\xB2\x2D";
         assert!(osc.into_iter().eq(by_hand.iter().copied()));
+        let from_macro = crate::osc!("/foo", 1000, -1, "hello", 1.234, 5.678)?;
+        assert!(from_macro.into_iter().eq(by_hand.iter().copied()));
         Ok(())
     }
 }
@@ -107,13 +101,20 @@ mod from_the_spec {
 #[cfg(feature = "quickcheck")]
 mod prop {
     use {
-        crate::{Address, Aligned4B, Decode, DynamicString, Message, Tag, Tags},
+        crate::{
+            Address, Aligned4B, Data, Decode, Dynamic, DynamicBlob, DynamicString, Message, Tag,
+            Tags, TimeTag,
+        },
         quickcheck::quickcheck,
     };
     quickcheck! {
         #[allow(unused_variables)]
         fn message_doesnt_panic(message: Message<Vec<String>>) -> bool { true }
 
+        fn encoded_len_matches_count(message: Message<Vec<String>>) -> bool {
+            message.encoded_len() == message.into_iter().count()
+        }
+
         fn four_byte_decode(v: Vec<u8>) -> bool {
             let size = v.len();
             let mut iter = v.into_iter();
@@ -122,13 +123,27 @@ mod prop {
                     return false;
                 }
             }
-            if (size % 4) == 0 {
+            if size.is_multiple_of(4) {
                 iter.next().is_none()
             } else {
                 Aligned4B::<core::convert::Infallible>::decode(&mut iter).is_err()
             }
         }
 
+        fn data_iter_matches_collected(original: Data) -> bool {
+            // What `Data::into_iter` used to do: collect into a `Vec` and re-iterate.
+            let collected: Vec<u8> = match original.clone() {
+                Data::Integer(i) => i.into_iter().collect(),
+                Data::Float(f) => f.into_iter().collect(),
+                Data::String(s) => s.into_iter().collect(),
+                Data::Blob(b) => b.into_iter().collect(),
+                #[cfg(feature = "nonstandard-types")]
+                Data::Nil => vec![],
+                Data::Unknown(u) => u.bytes().to_vec(),
+            };
+            original.into_iter().eq(collected)
+        }
+
         fn string_roundtrip(original: DynamicString) -> bool {
             let decoded = DynamicString::decode(&mut original.clone().into_iter());
             // println!("{original:#?} --> {decoded:#?}");
@@ -160,14 +175,16 @@ mod prop {
             true
         }
 
-        #[allow(clippy::as_conversions)]
+        fn time_tag_roundtrip(original: TimeTag) -> bool {
+            TimeTag::decode(&mut original.into_iter()) == Ok(original)
+        }
+
         fn tag_byte_roundtrip(tag: Tag) -> bool {
-            (tag as u8).try_into() == Ok(tag)
+            tag.as_byte().try_into() == Ok(tag)
         }
 
-        #[allow(clippy::as_conversions)]
         fn byte_tag_roundtrip(byte: u8) -> quickcheck::TestResult {
-            Tag::try_from(byte).map_or_else(|_| quickcheck::TestResult::passed(), |tag| quickcheck::TestResult::from_bool((tag as u8) == byte))
+            Tag::try_from(byte).map_or_else(|_| quickcheck::TestResult::passed(), |tag| quickcheck::TestResult::from_bool(tag.as_byte() == byte))
         }
 
         fn tags_byte_roundtrip(original: Tags) -> bool {
@@ -185,19 +202,125 @@ mod prop {
             true
         }
 
-        // TODO: all below
+        fn blob_roundtrip(original: DynamicBlob) -> bool {
+            DynamicBlob::decode(&mut original.clone().into_iter()) == Ok(original)
+        }
+
+        fn data_roundtrip(original: Data) -> bool {
+            // `Data` alone doesn't encode its type, so decode it behind a type tag.
+            let Ok(address) = Address::parse("/data") else { return false; };
+            let wrapped = Message::new(address, Dynamic(vec![original]));
+            let decoded = Message::decode(&mut wrapped.clone().into_iter());
+            // println!("{wrapped:#?} --> {decoded:#?}");
+            decoded == Ok(wrapped)
+        }
+
+        fn message_roundtrip(original: Message) -> bool {
+            let decoded = Message::decode(&mut original.clone().into_iter());
+            // println!("{original:#?} --> {decoded:#?}");
+            decoded == Ok(original)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod net {
+    use crate::IntoOsc;
+    use std::net::UdpSocket;
+
+    #[test]
+    fn loopback_roundtrip() {
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let message = (1.0_f32).into_osc([], "test").unwrap();
+        let sent =
+            crate::net::send(&sender, receiver.local_addr().unwrap(), message.clone()).unwrap();
+        let mut buffer = vec![0; crate::net::MAX_UDP_PAYLOAD];
+        let decoded = crate::net::recv(&receiver, &mut buffer).unwrap();
+        assert_eq!(sent, decoded.clone().into_iter().count());
+        assert!(decoded.into_iter().eq(message.clone()));
+        // Reusing the buffer, a shorter datagram doesn't pick up the last one's leftovers.
+        let short = ().into_osc([], "a").unwrap();
+        let _ = crate::net::send(&sender, receiver.local_addr().unwrap(), short.clone()).unwrap();
+        let decoded_short = crate::net::recv(&receiver, &mut buffer).unwrap();
+        assert!(decoded_short.into_iter().eq(short));
+        let mut trailing: Vec<u8> = message.into_iter().collect();
+        trailing.extend([0; 4]);
+        let _ = sender
+            .send_to(&trailing, receiver.local_addr().unwrap())
+            .unwrap();
+        assert_eq!(
+            crate::net::recv(&receiver, &mut buffer).unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod net_async {
+    use crate::{net::AsyncSocket, IntoOsc};
+
+    #[test]
+    fn loopback_roundtrip() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let sender = AsyncSocket::bind("127.0.0.1:0").await.unwrap();
+            let receiver = AsyncSocket::bind("127.0.0.1:0").await.unwrap();
+            let message = (0.25_f32).into_osc(["async"], "test").unwrap();
+            let sent = sender
+                .send(receiver.local_addr().unwrap(), message.clone())
+                .await
+                .unwrap();
+            let mut buffer = vec![0; crate::net::MAX_UDP_PAYLOAD];
+            let decoded = receiver.recv(&mut buffer).await.unwrap();
+            assert_eq!(sent, decoded.clone().into_iter().count());
+            assert!(decoded.into_iter().eq(message));
+        });
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod fuzz {
+    //! What `cargo fuzz` targets would do, but over a fixed set of pseudorandom inputs.
+
+    use crate::{Decode, Message, Packet};
+    use arbitrary::{Arbitrary, Unstructured};
 
-        // fn data_roundtrip(original: Data) -> bool {
-        //     let decoded = Data::decode(&mut original.clone().into_iter());
-        //     println!("{original:#?} --> {decoded:#?}");
-        //     decoded == Ok(original)
-        // }
+    /// Deterministic pseudorandom bytes (xorshift), so failures are reproducible.
+    fn inputs() -> impl Iterator<Item = Vec<u8>> {
+        (1_u64..=256).map(|seed| {
+            let mut state = seed;
+            core::iter::repeat_with(|| {
+                state ^= state << 13_u32;
+                state ^= state >> 7_u32;
+                state ^= state << 17_u32;
+                state.to_be_bytes()[0]
+            })
+            .take(usize::try_from(seed.saturating_mul(2)).unwrap())
+            .collect()
+        })
+    }
+
+    #[test]
+    fn decode_arbitrary_bytes_without_panicking() {
+        for input in inputs() {
+            let _packet = Packet::decode_slice(&input);
+            let _message = Message::decode_borrowed(&input);
+        }
+    }
 
-        // fn message_roundtrip(original: Message) -> bool {
-        //     let decoded = Message::decode(&mut original.clone().into_iter());
-        //     println!("{original:#?} --> {decoded:#?}");
-        //     decoded == Ok(original)
-        // }
+    #[test]
+    fn arbitrary_message_roundtrip() {
+        for input in inputs() {
+            let Ok(message) = Message::arbitrary(&mut Unstructured::new(&input)) else {
+                continue;
+            };
+            let bytes = message.canonical_bytes();
+            assert_eq!(Message::decode_slice(&bytes), Ok(message));
+        }
     }
 }
 
@@ -210,7 +333,9 @@ mod prop_reduced {
     #[allow(clippy::similar_names)]
     fn address_roundtrip_bytes_reduced_1() {
         let original = alloc::vec![47, 1, 0, 1];
-        let Ok(decoded) = Address::decode(&mut original.iter().copied()) else { return; };
+        let Ok(decoded) = Address::decode(&mut original.iter().copied()) else {
+            return;
+        };
         let recoded: Vec<_> = decoded.into_iter().collect();
         println!("{original:#?} --> {recoded:#?}");
         assert_eq!(recoded, original);
@@ -221,7 +346,9 @@ mod prop_reduced {
     #[allow(clippy::similar_names)]
     fn address_roundtrip_bytes_reduced_2() {
         let original = alloc::vec![47, 128, 0, 0];
-        let Ok(decoded) = Address::decode(&mut original.iter().copied()) else { return; };
+        let Ok(decoded) = Address::decode(&mut original.iter().copied()) else {
+            return;
+        };
         let recoded: Vec<_> = decoded.into_iter().collect();
         println!("{original:#?} --> {recoded:#?}");
         assert_eq!(recoded, original);
@@ -229,8 +356,2365 @@ mod prop_reduced {
 }
 
 mod unit {
+    use crate::{
+        atomic::{check_size_prefix, checked_blob_len},
+        decode_str_into, decode_tags_borrowed, padded_len, padded_len_checked,
+        valid_address_character, valid_address_character_with, Address, AddressCharPolicy,
+        AddressErr, Atomic, Batch, Blob, BlobDecodeErr, BorrowedData, DecodeConfig, Endian, Float,
+        Integer, IntoAddress, IntoAtomic, IntoIntoAddress, IntoOsc, InvalidContents, Message,
+        MessageQueue, Misaligned4B, StringDecodeErr, Tag, TagDecodeErr, TimeTag,
+    };
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[cfg(feature = "alloc")]
+    use crate::{
+        skip_prefix, AddressDecodeErr, BlobWriter, BundleDecodeErr, Data, DataVisitor, Decode,
+        DecodeErr, Dynamic, DynamicBlob, DynamicBundle, DynamicDecodeErr, DynamicString,
+        IncrementalDecoder, MessageBuilder, MessageDecodeErr, MessageStream, MissingTypeTags,
+        NegativeBlobSize, OscVersion, Packet, PacketDecodeErr, RawMessage, SchemaMismatch, Tags,
+        UnknownArg, UnknownArgErr,
+    };
+
+    #[test]
+    fn float_array() {
+        let msg = [1.0_f32, 2.0, 3.0].into_osc([], "xyz").unwrap();
+        assert!(msg.into_iter().eq(b"\
+            /xyz\0\0\0\0\
+            ,fff\0\0\0\0\
+            \x3F\x80\x00\x00\
+            \x40\x00\x00\x00\
+            \x40\x40\x00\x00"
+            .iter()
+            .copied()));
+    }
+
+    #[test]
+    fn empty_array() {
+        let msg = [0_i32; 0].into_osc([], "xyz").unwrap();
+        assert!(msg.into_iter().eq(b"/xyz\0\0\0\0,\0\0\0".iter().copied()));
+    }
+
+    #[test]
+    fn array_with_invalid_string() {
+        assert_eq!(
+            ["ok", "b\u{e9}d", "no\0"].into_osc([], "xyz").err(),
+            Some(AddressErr::StringErr(InvalidContents::NonAscii {
+                index: 1
+            }))
+        );
+    }
+
+    #[test]
+    fn infallible_atomics() {
+        assert!(Integer::from_i32(-1).into_iter().eq([0xFF; 4]));
+        assert_eq!(Integer::from(-1), Integer::from_i32(-1));
+        assert_eq!(Float::from(1.5), Float::from_f32(1.5));
+    }
+
+    #[test]
+    fn blob_reversed() {
+        let blob = Blob::try_from(b"abcde").unwrap();
+        let forward: Vec<u8> = blob.into_iter().collect();
+        let mut backward: Vec<u8> = blob.into_iter().rev().collect();
+        backward.reverse();
+        assert_eq!(forward, b"\0\0\0\x05abcde\0\0\0");
+        assert_eq!(backward, forward);
+        // Alternate ends, meeting in the middle of the padding.
+        let mut alternating = blob.into_iter();
+        assert_eq!(alternating.next_back(), Some(0));
+        assert_eq!(alternating.next(), Some(0));
+        assert!(alternating.eq(b"\0\0\x05abcde\0\0".iter().copied()));
+        let mut meeting = blob.into_iter();
+        assert_eq!(meeting.next_back(), Some(0));
+        assert!(meeting
+            .by_ref()
+            .take(10)
+            .eq(b"\0\0\0\x05abcde\0".iter().copied()));
+        assert_eq!(meeting.next_back(), Some(0));
+        assert_eq!(meeting.next_back(), None);
+        assert_eq!(meeting.next(), None);
+    }
+
+    #[test]
+    fn batched_back_then_front() {
+        let mut batched = b"abcd".iter().copied().batch();
+        assert_eq!(batched.next_back(), Some(b'd'));
+        assert_eq!(batched.next_back(), Some(b'c'));
+        assert_eq!(batched.len(), 2);
+        assert!(batched.eq(*b"ab"));
+        let mut unaligned = b"abcde".iter().copied().batch();
+        assert_eq!(unaligned.next_back(), Some(0));
+        assert_eq!(unaligned.next_back(), Some(0));
+        assert_eq!(unaligned.next_back(), Some(0));
+        assert_eq!(unaligned.next_back(), Some(b'e'));
+        assert!(unaligned.eq(*b"abcd"));
+    }
+
+    #[test]
+    fn blob_size_prefix_exact_size() {
+        let blob = Blob::try_from(b"\x01\x02\x03\x04\x05").unwrap();
+        assert!(blob
+            .into_iter()
+            .eq(*b"\0\0\0\x05\x01\x02\x03\x04\x05\0\0\0"));
+        assert_eq!(blob.into_iter().len(), 12);
+        // Several mixes of front and back (bit set means back) for several lengths, checking `len` after each step.
+        let data = [0xAA_u8; 9];
+        for n in 0..=data.len() {
+            let contents = data.get(..n).unwrap();
+            for pattern in [
+                0_u32, 0xFFFF, 0x5555, 0xAAAA, 0x00FF, 0xFF00, 0x0F0F, 0x1248,
+            ] {
+                let mut iter = Blob::try_from(contents).unwrap().into_iter();
+                let mut expected = iter.clone().count();
+                for step in 0..16 {
+                    assert_eq!(iter.len(), expected);
+                    let item = if (pattern >> step) & 1 == 0 {
+                        iter.next()
+                    } else {
+                        iter.next_back()
+                    };
+                    expected = expected.saturating_sub(usize::from(item.is_some()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn remaining_padding() {
+        let mut batched = b"abcde".iter().copied().batch();
+        assert_eq!(batched.remaining_padding(), 0);
+        assert!(batched.by_ref().take(5).eq(b"abcde".iter().copied()));
+        assert_eq!(batched.remaining_padding(), 3);
+        assert_eq!(batched.next(), Some(0));
+        assert_eq!(batched.remaining_padding(), 2);
+        assert_eq!(batched.next(), Some(0));
+        assert_eq!(batched.next(), Some(0));
+        assert_eq!(batched.remaining_padding(), 0);
+        assert_eq!(batched.next(), None);
+        let mut aligned = b"abcd".iter().copied().batch();
+        assert!(aligned.by_ref().take(4).eq(b"abcd".iter().copied()));
+        assert_eq!(aligned.remaining_padding(), 0);
+    }
+
+    #[test]
+    fn chained_batches_keep_padding() {
+        let mut first = b"abc".iter().copied().batch();
+        assert!(first.is_padded());
+        assert_eq!(first.next(), Some(b'a'));
+        assert!(!first.is_padded());
+        assert!(first.by_ref().take(3).eq(*b"bc\0"));
+        assert!(first.is_padded());
+        assert_eq!(first.next(), None);
+        let chained = b"abc"
+            .iter()
+            .copied()
+            .batch()
+            .chain(b"def".iter().copied().batch());
+        assert!(chained.eq(*b"abc\0def\0"));
+    }
+
+    #[test]
+    fn decode_borrowed_without_allocating() {
+        let msg = (1000_i32, "hello", 1.234_f32)
+            .into_osc(["a", "bc"], "d")
+            .unwrap();
+        let mut buf = [0_u8; 40];
+        let mut len = 0_usize;
+        for (slot, byte) in buf.iter_mut().zip(msg.clone()) {
+            *slot = byte;
+            len = len.saturating_add(1);
+        }
+        let bytes = buf.get(..len).unwrap();
+        let decoded = Message::decode_borrowed(bytes).unwrap();
+        assert!(decoded.address().segments().eq(["a", "bc"]));
+        assert_eq!(decoded.address().method(), "d");
+        assert!(decoded.data().iter().map(BorrowedData::type_tag).eq([
+            Tag::Integer,
+            Tag::String,
+            Tag::Float
+        ]));
+        assert!(matches!(
+            decoded.data().iter().next(),
+            Some(BorrowedData::Integer(i)) if i32::from(i) == 1000
+        ));
+        assert!(decoded.encoded_eq(&msg));
+        assert!(decoded.into_iter().eq(bytes.iter().copied()));
+        assert_eq!(
+            Message::decode_borrowed(buf.get(..len.saturating_add(4)).unwrap()),
+            Err(Misaligned4B::TrailingBytes)
+        );
+        assert_eq!(
+            Message::decode_borrowed(buf.get(..len.saturating_sub(4)).unwrap()),
+            Err(Misaligned4B::End)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn decode_borrowed_matches_owned() {
+        let bytes: Vec<u8> = (-7_i32, "xyz", 0.5_f32, "")
+            .into_osc(["left", "right"], "gain")
+            .unwrap()
+            .into_iter()
+            .collect();
+        let borrowed = Message::decode_borrowed(&bytes).unwrap();
+        let owned = Message::decode(&mut bytes.iter().copied()).unwrap();
+        assert!(borrowed.encoded_eq(&owned));
+        assert!(borrowed.address().segments().eq(owned.address().segments()));
+        assert_eq!(borrowed.address().method(), owned.address().method());
+        assert!(borrowed
+            .data()
+            .iter()
+            .map(BorrowedData::type_tag)
+            .eq(owned.data().tags()));
+        for malformed in [
+            &b"/a/\0,\0\0\0"[..],
+            b"//a\0,\0\0\0",
+            b"/\0\0\0,\0\0\0",
+            b"/a b\0\0\0\0,\0\0\0",
+            b"a/b\0,\0\0\0",
+        ] {
+            let borrowed_err = Message::decode_borrowed(malformed).map(|_message| ());
+            let owned_err = Message::decode(&mut malformed.iter().copied()).map(|_message| ());
+            assert!(borrowed_err.is_err());
+            assert_eq!(borrowed_err, owned_err);
+        }
+    }
+
+    #[test]
+    fn osc_macro_addresses() {
+        let nested = crate::osc!("/synth/1/gain", 0.5_f32).unwrap();
+        assert!(nested.encoded_eq(&0.5_f32.into_osc(["synth", "1"], "gain").unwrap()));
+        let bare = crate::osc!("/ping").unwrap();
+        assert!(bare.encoded_eq(&().into_osc([], "ping").unwrap()));
+        assert_eq!(
+            crate::osc!("ping").map(|_message| ()),
+            Err(AddressErr::MissingLeadingSlash)
+        );
+        assert_eq!(
+            crate::osc!("/a//b", 1_i32).map(|_message| ()),
+            Err(AddressErr::Empty)
+        );
+        assert_eq!(
+            crate::osc!("/a/", 1_i32).map(|_message| ()),
+            Err(AddressErr::Empty)
+        );
+    }
+
+    #[test]
+    fn narrow_and_unsigned_integers() {
+        let byte = 200_u8.into_osc([], "abc").unwrap();
+        assert_eq!(byte.data().0.into_rust(), 200);
+        let short = (-5_i16).into_osc([], "abc").unwrap();
+        assert_eq!(short.data().0.into_rust(), -5);
+        let unsigned = 0xFFFF_FFFF_u32.into_osc([], "abc").unwrap();
+        assert_eq!(unsigned.data().0.into_rust(), -1);
+        assert!(unsigned
+            .into_iter()
+            .eq(b"/abc\0\0\0\0,i\0\0\xFF\xFF\xFF\xFF".iter().copied()));
+    }
+
+    #[test]
+    #[cfg(feature = "nonstandard-types")]
+    fn optional_argument() {
+        let some = Some(5_i32).into_osc([], "abc").unwrap();
+        assert!(some
+            .into_iter()
+            .eq(b"/abc\0\0\0\0,i\0\0\0\0\0\x05".iter().copied()));
+        let none = None::<i32>.into_osc([], "abc").unwrap();
+        assert_eq!(none.encoded_len(), 12);
+        assert!(none.into_iter().eq(b"/abc\0\0\0\0,N\0\0".iter().copied()));
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            Dynamic::decode_slice(b",N\0\0"),
+            Ok(Dynamic(vec![Data::Nil]))
+        );
+    }
+
+    #[test]
+    fn atomic_byte_len() {
+        let string = "osc".into_atomic().unwrap();
+        assert_eq!(string.byte_len(), 4);
+        assert_eq!(string.byte_len(), string.into_iter().count());
+        let blob = (&b"\x01\x02\x03\x04\x05"[..]).into_atomic().unwrap();
+        assert_eq!(blob.byte_len(), 12);
+        assert_eq!(blob.byte_len(), blob.into_iter().count());
+        assert_eq!(1_i32.into_atomic().unwrap().byte_len(), 4);
+        assert_eq!(1.0_f32.into_atomic().unwrap().byte_len(), 4);
+    }
+
+    #[test]
+    fn time_tag_durations() {
+        use core::time::Duration;
+        let unix_epoch = TimeTag::from_duration_since_epoch(Duration::ZERO);
+        assert_eq!(unix_epoch, TimeTag::new(2_208_988_800, 0));
+        assert_eq!(unix_epoch.to_duration_since_epoch(), Some(Duration::ZERO));
+        // 2001-09-09T01:46:40.5Z
+        let billennium = Duration::from_millis(1_000_000_000_500);
+        let tag = TimeTag::from_duration_since_epoch(billennium);
+        assert_eq!(tag, TimeTag::new(3_208_988_800, 1 << 31));
+        assert_eq!(tag.to_duration_since_epoch(), Some(billennium));
+        let precise = Duration::new(1_700_000_000, 123_456_789);
+        assert_eq!(
+            TimeTag::from_duration_since_epoch(precise).to_duration_since_epoch(),
+            Some(precise)
+        );
+        // After the NTP era rolls over in 2036.
+        let later = Duration::from_secs(3_000_000_001);
+        assert_eq!(
+            TimeTag::from_duration_since_epoch(later).to_duration_since_epoch(),
+            Some(later)
+        );
+        assert_eq!(TimeTag::immediately().to_duration_since_epoch(), None);
+        assert_eq!(TimeTag::new(1 << 31, 0).to_duration_since_epoch(), None);
+    }
+
+    #[test]
+    fn from_tag_u8_roundtrip() {
+        for tag in [Tag::Integer, Tag::Float, Tag::String, Tag::Blob] {
+            assert_eq!(Tag::try_from(u8::from(tag)), Ok(tag));
+        }
+        assert_eq!(u8::from(Tag::String), b's');
+    }
+
+    #[test]
+    fn tag_characters() {
+        assert_eq!(Tag::Integer.as_char(), 'i');
+        assert_eq!(Tag::Blob.as_byte(), b'b');
+        assert_eq!(format!("{}", Tag::Float), "f");
+    }
+
+    #[test]
+    fn tag_payload_size() {
+        assert_eq!(Tag::Integer.payload_size(), Some(4));
+        assert_eq!(Tag::Float.payload_size(), Some(4));
+        assert_eq!(Tag::String.payload_size(), None);
+        assert_eq!(Tag::Blob.payload_size(), None);
+    }
+
+    #[test]
+    fn borrowed_string() {
+        assert_eq!(decode_str_into(b"data\0\0\0\0"), Ok(("data", 8)));
+        assert_eq!(decode_str_into(b"abc\0def\0"), Ok(("abc", 4)));
+        assert_eq!(
+            decode_str_into(b"data\0\0\0"),
+            Err(StringDecodeErr::Unterminated)
+        );
+        assert_eq!(
+            decode_str_into(b"da\0a"),
+            Err(StringDecodeErr::NullThenNonNull)
+        );
+        assert_eq!(
+            decode_str_into(b"\xFF\0\0\0"),
+            Err(StringDecodeErr::NonAscii(0xFF))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn compare_with_rust_types() {
+        let decoded_string =
+            DynamicString::decode(&mut b"expected\0\0\0\0".iter().copied()).unwrap();
+        assert_eq!(decoded_string, "expected");
+        assert_eq!(decoded_string, *"expected");
+        assert_eq!("expected", decoded_string);
+        assert_ne!(decoded_string, "unexpected");
+        let blob = DynamicBlob::try_from(vec![1, 2, 3]).unwrap();
+        assert_eq!(blob, [1_u8, 2, 3].as_slice());
+        assert_eq!(*[1_u8, 2, 3].as_slice(), blob);
+        assert_ne!(blob, [1_u8, 2].as_slice());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn lenient_padding() {
+        let string = b"ab\0X";
+        assert_eq!(
+            DynamicString::decode(&mut string.iter().copied()),
+            Err(Misaligned4B::Other(StringDecodeErr::NullThenNonNull))
+        );
+        assert_eq!(
+            DynamicString::decode_lenient(&mut string.iter().copied()).unwrap(),
+            "ab"
+        );
+        let address = b"/a\0X";
+        assert_eq!(
+            Address::decode(&mut address.iter().copied()),
+            Err(Misaligned4B::Other(AddressDecodeErr::NullThenNonNull))
+        );
+        assert_eq!(
+            Address::decode_lenient(&mut address.iter().copied()),
+            Ok(Address(vec![], "a".to_owned()))
+        );
+        let blob = b"\0\0\0\x03abcX";
+        assert_eq!(
+            DynamicBlob::decode(&mut blob.iter().copied()),
+            Err(Misaligned4B::Other(BlobDecodeErr::TooLong))
+        );
+        assert_eq!(
+            DynamicBlob::decode_lenient(&mut blob.iter().copied()).unwrap(),
+            b"abc".as_slice()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn error_offset() {
+        let bytes = b"/abc\0\0\0\0i\0\0\0";
+        assert_eq!(
+            Message::decode_at(&mut bytes.iter().copied()),
+            Err((
+                8,
+                Misaligned4B::Other(MessageDecodeErr::DataErr(DynamicDecodeErr::TypeTagErr(
+                    TagDecodeErr::MissingComma(b'i')
+                )))
+            ))
+        );
+        assert_eq!(
+            Message::decode_at(&mut bytes.iter().copied().take(10)),
+            Err((10, Misaligned4B::Misaligned))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn blob_size_limit() {
+        let huge = b"\x7F\xFF\xFF\xFFabcd";
+        assert_eq!(
+            DynamicBlob::decode_with_limit(&mut huge.iter().copied(), 16),
+            Err(Misaligned4B::Other(BlobDecodeErr::TooLarge))
+        );
+        // Without a limit, this just runs out of input (without allocating gigabytes first).
+        assert_eq!(
+            DynamicBlob::decode(&mut huge.iter().copied()),
+            Err(Misaligned4B::End)
+        );
+        let small = b"\0\0\0\x04abcd";
+        assert_eq!(
+            DynamicBlob::decode_with_limit(&mut small.iter().copied(), 4).unwrap(),
+            b"abcd".as_slice()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn blob_decode_reads_whole_words() {
+        // Reads the size, then exactly ceil(size / 4) words, leaving whatever follows alone.
+        let contents = b"abcdefghi";
+        for n in 0..=contents.len() {
+            let blob = DynamicBlob::try_from(contents.split_at(n).0.to_vec()).unwrap();
+            let mut encoded: Vec<u8> = blob.clone().into_iter().collect();
+            assert_eq!(encoded.len(), 4 + padded_len(n));
+            encoded.extend_from_slice(b"next");
+            let mut iter = encoded.into_iter();
+            assert_eq!(DynamicBlob::decode(&mut iter), Ok(blob));
+            assert!(iter.eq(*b"next"));
+        }
+        assert_eq!(
+            DynamicBlob::decode(&mut b"\0\0\0\x05abcde\0\0!".iter().copied()),
+            Err(Misaligned4B::Other(BlobDecodeErr::TooLong))
+        );
+        assert_eq!(
+            DynamicBlob::decode(&mut b"\0\0\0\x05abcde\0\0".iter().copied()),
+            Err(Misaligned4B::Misaligned)
+        );
+    }
+
+    #[test]
+    fn size_prefix_rejects_oversize_contents() {
+        assert_eq!(check_size_prefix(5), Ok(5));
+        assert_eq!(check_size_prefix(0x7FFF_FFFF), Ok(i32::MAX));
+        assert_eq!(
+            check_size_prefix(0x8000_0000),
+            Err(InvalidContents::TooLarge)
+        );
+        assert_eq!(
+            check_size_prefix(usize::MAX),
+            Err(InvalidContents::TooLarge)
+        );
+    }
+
+    #[test]
+    fn checked_blob_len_never_truncates() {
+        assert_eq!(checked_blob_len::<usize>(5), Ok((5, 8)));
+        assert_eq!(
+            checked_blob_len::<usize>(-1),
+            Err(BlobDecodeErr::NegativeSize)
+        );
+        assert_eq!(
+            checked_blob_len::<u64>(i32::MAX),
+            Ok((0x7FFF_FFFF, 0x8000_0000))
+        );
+        // Simulate a 16-bit `usize`.
+        assert_eq!(checked_blob_len::<u16>(0xFFFC), Ok((0xFFFC, 0xFFFC)));
+        assert_eq!(
+            checked_blob_len::<u16>(0xFFFD),
+            Err(BlobDecodeErr::SizeOverflow)
+        );
+        assert_eq!(
+            checked_blob_len::<u16>(i32::MAX),
+            Err(BlobDecodeErr::SizeOverflow)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn string_length_limit() {
+        let eight = b"12345678\0\0\0\0";
+        assert_eq!(
+            DynamicString::decode_with_limit(&mut eight.iter().copied(), 8).unwrap(),
+            "12345678"
+        );
+        // Never terminated: stops at the ninth character instead of reading forever.
+        assert_eq!(
+            DynamicString::decode_with_limit(&mut core::iter::repeat(b'x'), 8),
+            Err(Misaligned4B::Other(StringDecodeErr::TooLong))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn type_tag_count_limit() {
+        let many: Vec<u8> = core::iter::once(b',')
+            .chain(core::iter::repeat_n(b'i', 1000))
+            .chain(core::iter::once(b'\0'))
+            .batch()
+            .collect();
+        assert_eq!(
+            Tags::decode_with_limit(&mut many.iter().copied(), 16),
+            Err(Misaligned4B::Other(TagDecodeErr::TooMany))
+        );
+        assert_eq!(
+            Tags::decode_with_limit(&mut many.iter().copied(), 1000).map(|tags| tags.0.len()),
+            Ok(1000)
+        );
+        assert_eq!(
+            Tags::decode(&mut many.into_iter()).map(|tags| tags.0.len()),
+            Ok(1000)
+        );
+    }
+
+    #[test]
+    fn borrowed_message_queue() {
+        let a = b"/a\0\0,i\0\0\0\0\0\x01";
+        let b = b"/b\0\0,i\0\0\0\0\0\x02";
+        let c = b"/c\0\0,i\0\0\0\0\0\x03";
+        let mut queue = MessageQueue::<2>::new();
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+        for buf in [a, b, c] {
+            let message = Message::decode_borrowed(buf).unwrap();
+            if queue.is_full() {
+                assert_eq!(queue.pop().map(|m| m.address().method()), Some("a"));
+            }
+            assert_eq!(queue.push(message), Ok(()));
+        }
+        assert!(queue.is_full());
+        assert_eq!(
+            queue
+                .push(Message::decode_borrowed(a).unwrap())
+                .map_err(|m| m.address().method()),
+            Err("a")
+        );
+        assert_eq!(queue.pop().map(|m| m.address().method()), Some("b"));
+        assert_eq!(queue.pop().map(|m| m.address().method()), Some("c"));
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.len(), 0);
+        assert_eq!(queue.capacity(), 2);
+        let mut none = MessageQueue::<0>::default();
+        assert!(none.push(Message::decode_borrowed(a).unwrap()).is_err());
+    }
+
+    #[test]
+    fn string_contents_errors() {
+        assert_eq!(
+            crate::String::try_from("ab\u{e9}c\u{e9}"),
+            Err(InvalidContents::NonAscii { index: 2 })
+        );
+        assert_eq!(
+            crate::String::try_from("ab\0c"),
+            Err(InvalidContents::NullInString)
+        );
+        let split = |s| {
+            let (valid, rest) = crate::String::from_prefix(s);
+            (<&str>::from(valid), rest)
+        };
+        assert_eq!(split("hello, w\u{f6}rld"), ("hello, w", "\u{f6}rld"));
+        assert_eq!(split("a\0b"), ("a", "\0b"));
+        assert_eq!(split("fine"), ("fine", ""));
+    }
+
+    #[test]
+    #[allow(clippy::panic_in_result_fn)]
+    fn empty_message() -> Result<(), AddressErr> {
+        let ping = Message::empty([], "ping")?;
+        assert!(ping.into_iter().eq(b"/ping\0\0\0,\0\0\0".iter().copied()));
+        assert!(Message::new(Address::root("ping")?, ())
+            .into_iter()
+            .eq(b"/ping\0\0\0,\0\0\0".iter().copied()));
+        assert_eq!(Address::root(""), Err(AddressErr::Empty));
+        assert!(Message::empty(["a"], "b")?
+            .into_iter()
+            .eq(b"/a/b\0\0\0\0,\0\0\0".iter().copied()));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn missing_type_tags() {
+        let bare = b"/ping\0\0\0";
+        assert_eq!(
+            Message::decode(&mut bare.iter().copied()),
+            Err(Misaligned4B::End),
+        );
+        let lenient = DecodeConfig::default().with_missing_type_tags(MissingTypeTags::NoArguments);
+        let ping = Message::decode_with_config(&mut bare.iter().copied(), lenient).unwrap();
+        assert_eq!(ping.address().method(), "ping");
+        assert!(ping.data().is_empty());
+        // Anything else after the address still needs type tags.
+        let mut untyped = [0_u8, 0, 0, 1, 0, 0, 0, 2].into_iter();
+        assert_eq!(
+            Dynamic::decode_with_config(&mut untyped, lenient),
+            Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(
+                TagDecodeErr::MissingComma(0)
+            )))
+        );
+        assert_eq!(untyped.next(), Some(0));
+        // Peeking leaves the next message in a stream alone.
+        let stream = b"/ping\0\0\0/pong\0\0\0,\0\0\0";
+        let mut rest = stream.iter().copied().peekable();
+        let address = Address::decode(&mut rest).unwrap();
+        assert_eq!(address.method(), "ping");
+        assert_eq!(
+            Dynamic::decode_allow_untyped(&mut rest),
+            Ok(Dynamic::default())
+        );
+        let second = Message::decode(&mut rest).unwrap();
+        assert_eq!(second.address().method(), "pong");
+        assert_eq!(rest.next(), None);
+        assert_eq!(
+            Dynamic::decode_allow_untyped(&mut b",i\0\0\0\0\0\x07".iter().copied().peekable()),
+            Ok(Dynamic::from_iter([Data::Integer(7_i32.into())])),
+        );
+        assert_eq!(
+            Dynamic::decode_allow_untyped(&mut core::iter::empty().peekable()),
+            Ok(Dynamic::default())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn decode_expecting_schema() {
+        let body = b",if\0\0\0\0\x01\0\0\0\0";
+        let expected = Tags::parse(",is").unwrap();
+        assert_eq!(
+            Dynamic::decode_expecting(&mut body.iter().copied(), &expected),
+            Err(Misaligned4B::Other(DynamicDecodeErr::Schema(
+                SchemaMismatch::Type {
+                    index: 1,
+                    actual: b'f',
+                }
+            ))),
+        );
+        assert_eq!(
+            Dynamic::decode_expecting(&mut body.iter().copied(), &Tags::parse(",i").unwrap()),
+            Err(Misaligned4B::Other(DynamicDecodeErr::Schema(
+                SchemaMismatch::Count {
+                    expected: 1,
+                    actual: 2,
+                }
+            ))),
+        );
+        assert_eq!(
+            Dynamic::decode_expecting(&mut body.iter().copied(), &Tags::parse(",if").unwrap()),
+            Dynamic::decode(&mut body.iter().copied()),
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn raw_message_passthrough() {
+        let encoded = (1_i32, "hi").into_osc(["a"], "b").unwrap().to_vec();
+        let raw = RawMessage::decode(&mut encoded.iter().copied()).unwrap();
+        assert_eq!(raw.as_slice(), encoded.as_slice());
+        assert!(raw.clone().into_iter().eq(encoded.iter().copied()));
+        assert_eq!(RawMessage::new(encoded.clone()), raw);
+        assert_eq!(
+            RawMessage::decode(&mut core::iter::empty()),
+            Err(Misaligned4B::End)
+        );
+        assert_eq!(
+            RawMessage::decode(&mut encoded.iter().copied().take(encoded.len() - 1)),
+            Err(Misaligned4B::Misaligned)
+        );
+        // Concatenated messages come out one at a time, arguments untouched.
+        let untouched = b"/x\0\0,s\0\0\xE9\0\0\0";
+        let mut stream = encoded.iter().chain(untouched).copied();
+        assert_eq!(RawMessage::decode(&mut stream), Ok(raw));
+        assert_eq!(
+            RawMessage::decode(&mut stream)
+                .map(RawMessage::into_iter)
+                .map(Iterator::collect::<Vec<u8>>),
+            Ok(untouched.to_vec())
+        );
+        assert_eq!(RawMessage::decode(&mut stream), Err(Misaligned4B::End));
+        assert_eq!(
+            RawMessage::decode(&mut b"#bundle\0".iter().copied()),
+            Err(Misaligned4B::Other(MessageDecodeErr::AddressErr(
+                AddressDecodeErr::LeadingSlash { actual: b'#' }
+            )))
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "nonstandard-types"))]
+    fn array_bracket_tags() {
+        let tags = Tags::decode(&mut b",[ff]\0\0\0".iter().copied()).unwrap();
+        assert!(tags
+            .iter()
+            .copied()
+            .eq([Tag::ArrayOpen, Tag::Float, Tag::Float, Tag::ArrayClose,]));
+        assert!(tags.into_iter().eq(*b",[ff]\0\0\0"));
+        assert_eq!(Tag::try_from(b']'), Ok(Tag::ArrayClose));
+        // Arrays aren't standard in any version, and can't be read as arguments yet either way.
+        let empty_array = b",[]\0";
+        assert_eq!(
+            Dynamic::decode(&mut empty_array.iter().copied()),
+            Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(
+                TagDecodeErr::NotInVersion(b'[')
+            ))),
+        );
+        assert_eq!(
+            Dynamic::decode_skipping_unknown(&mut empty_array.iter().copied(), |_| None),
+            Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(
+                TagDecodeErr::ArraysNotYetImplemented(b'[')
+            ))),
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn normalized_addresses() {
+        let built = alloc::vec!["a".to_owned(), "b".to_owned()]
+            .into_address("c".to_owned())
+            .unwrap();
+        let parsed = Address::parse("/a/b/c").unwrap();
+        assert_eq!(built.normalized(), parsed.normalized());
+        assert_eq!(
+            ["a", "b"].into_address("c").unwrap().normalized(),
+            ["a", "b", "c"]
+        );
+        assert_eq!(Address::root("ping").unwrap().normalized(), ["ping"]);
+        assert_ne!(
+            Address::parse("/a/bc").unwrap().normalized(),
+            parsed.normalized()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn to_vec_exact_capacity() {
+        let message = (1_i32, "hello", 2.0_f32)
+            .into_osc(["synth"], "note")
+            .unwrap();
+        let v = message.to_vec();
+        assert_eq!(v, message.clone().into_iter().collect::<Vec<u8>>());
+        assert_eq!(v.capacity(), v.len());
+        assert_eq!(v.len(), message.encoded_len());
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc"))]
+    fn borrowed_tuple_without_alloc() {
+        const EXPECTED: &[u8] = b"/a/b\0\0\0\0,isb\0\0\0\0\0\0\0\x07hi\0\0\0\0\0\x03\x01\x02\x03\0";
+        let bytes: &[u8] = &[1, 2, 3];
+        let converted = (7_i32, "hi", bytes).into_osc(["a"], "b").unwrap();
+        assert!(converted.into_iter().eq(EXPECTED.iter().copied()));
+        let atomics = (
+            Integer::from(7),
+            crate::String::try_from("hi").unwrap(),
+            Blob::try_from(bytes).unwrap(),
+        );
+        let message = Message::new(["a"].into_address("b").unwrap(), atomics);
+        assert_eq!(message.clone().into_iter().count(), EXPECTED.len());
+        assert!(message.into_iter().eq(EXPECTED.iter().copied()));
+    }
+
+    #[test]
+    fn encoders_stay_exhausted() {
+        fn drain<I: core::iter::FusedIterator<Item = u8>>(mut iter: I) {
+            while iter.next().is_some() {}
+            for _ in 0_u8..5 {
+                assert_eq!(iter.next(), None);
+            }
+        }
+        drain([1_u8, 2, 3, 4, 5].into_iter().batch());
+        drain(["a", "b"].into_address("c").unwrap().into_iter());
+        drain(Blob::try_from(&[1_u8, 2, 3]).unwrap().into_iter());
+        drain((1_i32, 2.0_f32).into_osc(["a"], "b").unwrap().into_iter());
+    }
+
+    #[test]
+    fn raw_atomic_bytes() {
+        let raw = [0x3F, 0x80, 0, 0];
+        assert_eq!(Integer::from_be_bytes(raw).to_be_bytes(), raw);
+        assert_eq!(Float::from_be_bytes(raw).to_be_bytes(), raw);
+        assert_eq!(i32::from(Integer::from_be_bytes(raw)), 0x3F80_0000);
+        assert!((f32::from(Float::from_be_bytes(raw)) - 1.0_f32).abs() < f32::EPSILON);
+        assert_eq!(Integer::from_i32(-2).to_be_bytes(), (-2_i32).to_be_bytes());
+        assert!(Float::from_be_bytes(raw).into_iter().eq(raw));
+    }
+
+    #[test]
+    fn tag_eq_byte() {
+        assert_eq!(Tag::Integer, b'i');
+        assert_eq!(b'f', Tag::Float);
+        assert_ne!(Tag::String, b'b');
+        assert_ne!(b's', Tag::Blob);
+    }
+
+    #[test]
+    fn incomplete_vs_fatal() {
+        assert!(Misaligned4B::<StringDecodeErr>::End.is_incomplete());
+        assert!(!Misaligned4B::<StringDecodeErr>::Misaligned.is_incomplete());
+        assert!(!Misaligned4B::<StringDecodeErr>::TrailingBytes.is_incomplete());
+        assert!(!Misaligned4B::Other(StringDecodeErr::NonAscii(0xFF)).is_incomplete());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn single_segment_address_round_trip() {
+        for (method, encoded) in [
+            ("p", &b"/p\0\0"[..]),
+            ("pi", b"/pi\0"),
+            ("pin", b"/pin\0\0\0\0"),
+            ("ping", b"/ping\0\0\0"),
+        ] {
+            let address = [].into_address(method).unwrap();
+            assert!(address.clone().into_iter().eq(encoded.iter().copied()));
+            let decoded = Address::decode(&mut encoded.iter().copied()).unwrap();
+            assert!(decoded.segments().is_empty());
+            assert_eq!(decoded.method(), method);
+            let text = core::str::from_utf8(encoded).unwrap();
+            assert_eq!(
+                Address::parse(text.trim_end_matches('\0')),
+                Ok(decoded.clone())
+            );
+            assert!(decoded.into_iter().eq(address));
+        }
+        assert_eq!(
+            Address::decode(&mut b"/\0\0\0".iter().copied()),
+            Err(Misaligned4B::Other(AddressDecodeErr::NoMethod)),
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn blob_writer_chunks() {
+        use core::fmt::Write as _;
+        let mut writer = BlobWriter::new();
+        writer.push_bytes(&[1, 2]);
+        writer.push_bytes(&[3]);
+        write!(writer, "{}", 45_i32).unwrap();
+        assert_eq!(writer.len(), 5);
+        let blob = writer.finish().unwrap();
+        assert_eq!(blob, [1, 2, 3, b'4', b'5'][..]);
+        assert!(blob
+            .into_iter()
+            .eq([0, 0, 0, 5, 1, 2, 3, b'4', b'5', 0, 0, 0]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn logical_tags() {
+        let tags = Tags::decode(&mut b",if\0".iter().copied()).unwrap();
+        assert!(tags.iter().copied().eq([Tag::Integer, Tag::Float]));
+        assert!(tags.clone().into_iter().eq(*b",if\0"));
+        assert!(tags.into_tags().eq([Tag::Integer, Tag::Float]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn bundle_nesting_limit() {
+        let message = Message::new(Address::parse("/deep").unwrap(), Dynamic(vec![]));
+        let mut bundle = DynamicBundle::new(TimeTag::immediately(), vec![message.into()]).unwrap();
+        for _ in 1..40 {
+            bundle = DynamicBundle::new(TimeTag::immediately(), vec![bundle.into()]).unwrap();
+        }
+        let bytes: Vec<u8> = bundle.clone().into_iter().collect();
+        assert_eq!(
+            DynamicBundle::decode(&mut bytes.iter().copied()),
+            Err(Misaligned4B::Other(BundleDecodeErr::TooDeep))
+        );
+        assert_eq!(
+            Packet::decode(&mut bytes.iter().copied()),
+            Err(Misaligned4B::Other(PacketDecodeErr::BundleErr(
+                BundleDecodeErr::TooDeep
+            )))
+        );
+        let config = DecodeConfig::default().with_max_depth(39);
+        assert_eq!(
+            DynamicBundle::decode_with_config(&mut bytes.iter().copied(), config),
+            Err(Misaligned4B::Other(BundleDecodeErr::TooDeep))
+        );
+        assert_eq!(
+            DynamicBundle::decode_with_config(&mut bytes.into_iter(), config.with_max_depth(40)),
+            Ok(bundle)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn skip_arguments_for_passthrough() {
+        let tags = Tags::parse(",ifsb").unwrap();
+        let payload = b"\0\0\0\x01\x3F\x80\0\0hello\0\0\0\0\0\0\x05abcde\0\0\0";
+        let mut iter = payload.iter().copied().chain(*b"next");
+        assert_eq!(tags.skip_arguments(&mut iter), Ok(payload.len()));
+        assert!(iter.eq(*b"next"));
+        assert_eq!(
+            Tags::parse(",s")
+                .unwrap()
+                .skip_arguments(&mut b"ab\0c".iter().copied()),
+            Err(Misaligned4B::Other(DynamicDecodeErr::StringErr {
+                index: 0,
+                err: StringDecodeErr::NullThenNonNull,
+            }))
+        );
+        assert_eq!(
+            tags.skip_arguments(&mut payload[..20].iter().copied()),
+            Err(Misaligned4B::End)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn sort_by_encoded_bytes() {
+        let parse =
+            |address: &str, data: Dynamic| Message::new(Address::parse(address).unwrap(), data);
+        let mut messages = [
+            parse("/b", Dynamic(vec![Data::from(1_i32)])),
+            parse("/a/z", Dynamic(vec![])),
+            parse("/b", Dynamic(vec![Data::from(1.0_f32)])),
+        ];
+        messages.sort_by(|a, b| a.cmp_encoded(b));
+        let encoded: Vec<Vec<u8>> = messages.iter().map(Message::canonical_bytes).collect();
+        let mut sorted = encoded.clone();
+        sorted.sort();
+        assert_eq!(encoded, sorted);
+        assert_eq!(messages.first().map(|m| m.address().method()), Some("z"));
+        // ",f" sorts before ",i", even though `Data::Integer` comes first structurally.
+        assert_eq!(
+            messages.get(1).map(|m| m.data().tags().collect::<Vec<_>>()),
+            Some(vec![b'f'])
+        );
+    }
+
+    #[test]
     #[cfg(feature = "alloc")]
-    use crate::{Decode, Tag, Tags};
+    fn reply_to_ping() {
+        let ping = Message::decode(&mut b"/ping\0\0\0,\0\0\0".iter().copied()).unwrap();
+        let reply = ping.reply_with((1_i32.into_atomic().unwrap(),));
+        assert_eq!(reply.address(), ping.address());
+        assert!(reply
+            .into_iter()
+            .eq(b"/ping\0\0\0,i\0\0\0\0\0\x01".iter().copied()));
+    }
+
+    #[test]
+    fn borrowed_type_tags() {
+        assert!(decode_tags_borrowed(b",ifsb\0").eq([
+            Ok(Tag::Integer),
+            Ok(Tag::Float),
+            Ok(Tag::String),
+            Ok(Tag::Blob),
+        ]));
+        assert!(decode_tags_borrowed(b",i\0\0").eq([Ok(Tag::Integer)]));
+        assert!(decode_tags_borrowed(b",").eq([]));
+        assert!(decode_tags_borrowed(b"").eq([]));
+        assert!(decode_tags_borrowed(b"if").eq([Err(TagDecodeErr::MissingComma(b'i'))]));
+        assert!(decode_tags_borrowed(b",i?f").eq([
+            Ok(Tag::Integer),
+            Err(TagDecodeErr::UnrecognizedTypeTag(b'?'))
+        ]));
+    }
+
+    #[test]
+    fn padding_lengths() {
+        for (len, padded) in [(0, 0), (1, 4), (3, 4), (4, 4), (5, 8)] {
+            assert_eq!(padded_len(len), padded);
+            assert_eq!(padded_len_checked(len), Some(padded));
+        }
+        assert_eq!(padded_len_checked(usize::MAX - 3), Some(usize::MAX - 3));
+        assert_eq!(padded_len_checked(usize::MAX - 2), None);
+        assert_eq!(padded_len_checked(usize::MAX), None);
+    }
+
+    #[test]
+    #[allow(clippy::indexing_slicing)]
+    fn blob_slicing() {
+        let message = Message::decode_borrowed(b"/b\0\0,b\0\0\0\0\0\x06abcdef\0\0").unwrap();
+        let blob = message
+            .data()
+            .iter()
+            .find_map(|arg| {
+                if let BorrowedData::Blob(blob) = arg {
+                    Some(blob)
+                } else {
+                    None
+                }
+            })
+            .unwrap();
+        assert_eq!(blob.len(), 6);
+        assert!(!blob.is_empty());
+        assert_eq!(&blob[1..5], b"bcde");
+        assert_eq!(blob[2], b'c');
+        assert_eq!(blob.get(4..), Some(&b"ef"[..]));
+        assert_eq!(blob.get(5..9), None);
+        assert!(Blob::try_from(&[][..]).is_ok_and(|empty| empty.is_empty()));
+        #[cfg(feature = "alloc")]
+        {
+            let bytes = b"\0\0\0\x06abcdef\0\0";
+            let owned = DynamicBlob::decode(&mut bytes.iter().copied()).unwrap();
+            assert_eq!(owned.len(), 6);
+            assert!(!owned.is_empty());
+            assert_eq!(&owned[1..5], b"bcde");
+            assert_eq!(owned.get(6), None);
+            assert_eq!(owned.get(..=1), Some(&b"ab"[..]));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn unified_decode_errors() {
+        let address = AddressDecodeErr::NullThenNonNull;
+        let tags = TagDecodeErr::MissingComma(b'i');
+        let string = StringDecodeErr::NonAscii(0xFF);
+        let blob = BlobDecodeErr::NegativeSize;
+        assert_eq!(DecodeErr::from(address), DecodeErr::Address(address));
+        assert_eq!(DecodeErr::from(tags), DecodeErr::Tags(tags));
+        assert_eq!(DecodeErr::from(string), DecodeErr::String(string));
+        assert_eq!(DecodeErr::from(blob), DecodeErr::Blob(blob));
+        assert_eq!(
+            DecodeErr::from(MessageDecodeErr::DataErr(DynamicDecodeErr::BlobErr {
+                index: 2,
+                err: blob,
+            })),
+            DecodeErr::Blob(blob)
+        );
+        assert_eq!(
+            DecodeErr::from(PacketDecodeErr::BundleErr(BundleDecodeErr::MessageErr(
+                MessageDecodeErr::AddressErr(address)
+            ))),
+            DecodeErr::Address(address)
+        );
+        assert_eq!(
+            DecodeErr::from(BundleDecodeErr::SizeMismatch),
+            DecodeErr::Bundle(BundleDecodeErr::SizeMismatch)
+        );
+        assert_eq!(
+            DecodeErr::from(Misaligned4B::Other(tags)),
+            DecodeErr::Tags(tags)
+        );
+        assert_eq!(
+            DecodeErr::from(Misaligned4B::<MessageDecodeErr>::End),
+            DecodeErr::End
+        );
+        let decode = |bytes: &[u8]| -> Result<Packet, DecodeErr> {
+            Ok(Packet::decode(&mut bytes.iter().copied())?)
+        };
+        assert_eq!(decode(b"?abc"), Err(DecodeErr::UnrecognizedStart(b'?')));
+        assert_eq!(decode(b"/ab"), Err(DecodeErr::Misaligned));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn blob_chunks_stream() {
+        let waveform: Vec<u8> = (0_u8..=250).collect();
+        let encoded: Vec<u8> = DynamicBlob(waveform).into_iter().collect();
+        let mut sum = 0_u32;
+        let mut chunks = 0_usize;
+        assert_eq!(
+            DynamicBlob::decode_chunks(&mut encoded.iter().copied(), |chunk| {
+                sum = chunk
+                    .iter()
+                    .map(|&b| u32::from(b))
+                    .fold(sum, u32::saturating_add);
+                chunks = chunks.saturating_add(1);
+            }),
+            Ok(251)
+        );
+        assert_eq!(chunks, 63);
+        let full = DynamicBlob::decode(&mut encoded.into_iter()).unwrap();
+        assert_eq!(
+            sum,
+            full.as_slice()
+                .iter()
+                .map(|&b| u32::from(b))
+                .fold(0, u32::saturating_add)
+        );
+        let bad_padding = b"\0\0\0\x02ab!\0";
+        assert_eq!(
+            DynamicBlob::decode_chunks(&mut bad_padding.iter().copied(), |_chunk| {}),
+            Err(Misaligned4B::Other(BlobDecodeErr::TooLong))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn named_parameters_interleaved() {
+        let ints = alloc::collections::BTreeMap::from([("width", 640_i32), ("height", 480_i32)]);
+        assert!(ints.into_osc([], "size").unwrap().into_iter().eq(
+            b"/size\0\0\0,sisi\0\0\0height\0\0\0\0\x01\xE0width\0\0\0\0\0\x02\x80"
+                .iter()
+                .copied()
+        ));
+        let floats = alloc::collections::BTreeMap::from([("gain", 0.5_f32)]);
+        assert!(floats
+            .into_osc([], "mix")
+            .unwrap()
+            .data()
+            .tags()
+            .eq([Tag::String, Tag::Float]));
+        let bad = alloc::collections::BTreeMap::from([("caf\u{e9}", 1_i32)]);
+        assert_eq!(
+            bad.into_osc([], "x").map(|_message| ()),
+            Err(AddressErr::StringErr(InvalidContents::NonAscii {
+                index: 3
+            }))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn negative_blob_size_policy() {
+        let bytes = b",bi\0\x80\0\0\0\0\0\0\x2A";
+        assert_eq!(
+            Dynamic::decode(&mut bytes.iter().copied()),
+            Err(Misaligned4B::Other(DynamicDecodeErr::BlobErr {
+                index: 0,
+                err: BlobDecodeErr::NegativeSize,
+            }))
+        );
+        let lenient =
+            DecodeConfig::default().with_negative_blob_size(NegativeBlobSize::ClampToZero);
+        assert_eq!(
+            Dynamic::decode_with_config(&mut bytes.iter().copied(), lenient),
+            Ok(Dynamic(vec![
+                Data::Blob(DynamicBlob(vec![])),
+                Data::Integer(42.into())
+            ]))
+        );
+        assert_eq!(
+            DynamicBlob::decode_with_config(
+                &mut bytes[4..].iter().copied(),
+                DecodeConfig::default()
+            ),
+            Err(Misaligned4B::Other(BlobDecodeErr::NegativeSize))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn counting_visitor() {
+        #[derive(Default)]
+        struct Counter {
+            ints: i32,
+            floats: f32,
+            chars: usize,
+            bytes: usize,
+        }
+        impl DataVisitor for Counter {
+            fn visit_int(&mut self, i: i32) {
+                self.ints = self.ints.saturating_add(i);
+            }
+            fn visit_float(&mut self, f: f32) {
+                self.floats += f;
+            }
+            fn visit_str(&mut self, s: &str) {
+                self.chars = self.chars.saturating_add(s.len());
+            }
+            fn visit_blob(&mut self, b: &[u8]) {
+                self.bytes = self.bytes.saturating_add(b.len());
+            }
+        }
+        let mut dynamic = Dynamic::default();
+        dynamic.push_int(7);
+        dynamic.push_float(0.5);
+        dynamic.push_str("hello").unwrap();
+        dynamic.push_blob(b"\x01\x02\x03").unwrap();
+        assert!(dynamic
+            .tags()
+            .eq([Tag::Integer, Tag::Float, Tag::String, Tag::Blob]));
+        let mut counter = Counter::default();
+        dynamic.accept(&mut counter);
+        dynamic.accept(&mut counter);
+        assert_eq!(counter.ints, 14);
+        assert!((counter.floats - 1.0).abs() < f32::EPSILON);
+        assert_eq!(counter.chars, 10);
+        assert_eq!(counter.bytes, 6);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn argument_index_in_errors() {
+        let bad_padding = b",ib\0\0\0\0\x05\0\0\0\x03abc!";
+        let expected = DynamicDecodeErr::BlobErr {
+            index: 1,
+            err: BlobDecodeErr::TooLong,
+        };
+        assert_eq!(
+            Dynamic::decode(&mut bad_padding.iter().copied()),
+            Err(Misaligned4B::Other(expected))
+        );
+        assert_eq!(
+            expected.to_string(),
+            format!(
+                "Argument 1 (a blob) was malformed: {}",
+                BlobDecodeErr::TooLong
+            )
+        );
+        let mut message = b"/abc\0\0\0\0".to_vec();
+        message.extend_from_slice(bad_padding);
+        assert_eq!(
+            Message::decode_borrowed(&message).map(|_message| ()),
+            Err(Misaligned4B::Other(MessageDecodeErr::DataErr(expected)))
+        );
+        let negative = b",ib\0\0\0\0\x05\xFF\xFF\xFF\xFF";
+        assert_eq!(
+            Dynamic::decode(&mut negative.iter().copied()),
+            Err(Misaligned4B::Other(DynamicDecodeErr::BlobErr {
+                index: 1,
+                err: BlobDecodeErr::NegativeSize,
+            }))
+        );
+        assert_eq!(
+            DynamicDecodeErr::from(BlobDecodeErr::TooLong),
+            DynamicDecodeErr::BlobErr {
+                index: 0,
+                err: BlobDecodeErr::TooLong,
+            }
+        );
+        assert_eq!(
+            DynamicDecodeErr::from(StringDecodeErr::NonAscii(0x80)),
+            DynamicDecodeErr::StringErr {
+                index: 0,
+                err: StringDecodeErr::NonAscii(0x80),
+            }
+        );
+        // Running out of bytes isn't any one argument's fault.
+        let truncated = b",ib\0\0\0\0\x05\0\0\0\x08abcd";
+        assert_eq!(
+            Dynamic::decode(&mut truncated.iter().copied()),
+            Err(Misaligned4B::End)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn skip_transport_prefix() {
+        let framed = b"\0\0\0\x10/abc\0\0\0\0,i\0\0\0\0\0\x05";
+        let mut iter = framed.iter().copied();
+        assert_eq!(skip_prefix(&mut iter, 4), Ok(()));
+        let message = Message::decode(&mut iter).unwrap();
+        assert!(message.encoded_eq(&5_i32.into_osc([], "abc").unwrap()));
+        assert_eq!(
+            skip_prefix(&mut b"\0\0".iter().copied(), 4),
+            Err(Misaligned4B::End)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn decode_exact_slice() {
+        assert_eq!(
+            DynamicString::decode_slice(b"data\0\0\0\0").unwrap(),
+            "data"
+        );
+        assert_eq!(
+            DynamicString::decode_slice(b"data\0\0\0\0junk"),
+            Err(Misaligned4B::TrailingBytes)
+        );
+        assert_eq!(
+            Integer::decode_slice(b"\0\0\0\x01\0"),
+            Err(Misaligned4B::TrailingBytes)
+        );
+        assert_eq!(
+            DynamicString::decode_slice(b"data\0\0"),
+            Err(Misaligned4B::Misaligned)
+        );
+    }
+
+    #[test]
+    fn address_char_policies() {
+        assert!(!valid_address_character(0xC3));
+        assert!(!valid_address_character_with(
+            0xC3,
+            AddressCharPolicy::Strict
+        ));
+        assert!(!valid_address_character_with(
+            0xC3,
+            AddressCharPolicy::SpecOscString
+        ));
+        assert!(valid_address_character_with(
+            0xC3,
+            AddressCharPolicy::AllowUtf8Continuation
+        ));
+        assert!(!valid_address_character(0x7F));
+        assert!(valid_address_character_with(
+            0x7F,
+            AddressCharPolicy::SpecOscString
+        ));
+        for policy in [
+            AddressCharPolicy::Strict,
+            AddressCharPolicy::SpecOscString,
+            AddressCharPolicy::AllowUtf8Continuation,
+        ] {
+            assert!(valid_address_character_with(b'a', policy));
+            assert!(!valid_address_character_with(b'/', policy));
+            assert!(!valid_address_character_with(b'\0', policy));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn address_char_policy_when_building_and_decoding() {
+        let utf8 = AddressCharPolicy::AllowUtf8Continuation;
+        assert_eq!(
+            Address::parse("/caf\u{e9}"),
+            Err(AddressErr::InvalidCharacter(0xC3))
+        );
+        let address = Address::parse_with("/caf\u{e9}", utf8).unwrap();
+        assert_eq!(address.method(), "caf\u{e9}");
+        assert_eq!(
+            ["caf\u{e9}"].into_address("x"),
+            Err(AddressErr::InvalidCharacter(0xC3))
+        );
+        let _ = ["caf\u{e9}"].into_address_with("x", utf8).unwrap();
+        let bytes: Vec<u8> = address.clone().into_iter().collect();
+        assert_eq!(
+            Address::decode_with_config(&mut bytes.iter().copied(), DecodeConfig::default()),
+            Err(Misaligned4B::Other(AddressDecodeErr::NotPrintableAscii(
+                0xC3
+            )))
+        );
+        let config = DecodeConfig::default().with_address_chars(utf8);
+        assert_eq!(
+            Address::decode_with_config(&mut bytes.iter().copied(), config),
+            Ok(address.clone())
+        );
+        // Every byte is allowed on its own, but together they aren't UTF-8.
+        assert_eq!(
+            Address::decode_with_config(&mut b"/\xC3x\0".iter().copied(), config),
+            Err(Misaligned4B::Other(AddressDecodeErr::InvalidUtf8))
+        );
+        let message = Message::new(address, Dynamic(vec![]));
+        let encoded: Vec<u8> = message.clone().into_iter().collect();
+        assert_eq!(
+            Message::decode_with_config(&mut encoded.iter().copied(), DecodeConfig::default()),
+            Err(Misaligned4B::Other(MessageDecodeErr::AddressErr(
+                AddressDecodeErr::NotPrintableAscii(0xC3)
+            )))
+        );
+        assert_eq!(
+            Message::decode_with_config(&mut encoded.iter().copied(), config),
+            Ok(message)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse_address() {
+        let address = Address::parse("/a/b/c").unwrap();
+        assert_eq!(address.segments(), ["a", "b"]);
+        assert_eq!(address.method(), "c");
+        assert!(Address::parse("/c").unwrap().segments().is_empty());
+        assert_eq!(Address::parse("a/b"), Err(AddressErr::MissingLeadingSlash));
+        assert_eq!(Address::parse("/a/b/"), Err(AddressErr::Empty));
+        assert_eq!(Address::parse("/a//b"), Err(AddressErr::Empty));
+        assert_eq!(Address::parse("/"), Err(AddressErr::Empty));
+        assert_eq!(
+            Address::parse("/a b"),
+            Err(AddressErr::InvalidCharacter(b' '))
+        );
+    }
+
+    #[test]
+    fn empty_segments_and_methods() {
+        assert_eq!([""; 0].into_address(""), Err(AddressErr::Empty));
+        assert_eq!([""].into_address("x"), Err(AddressErr::Empty));
+        assert_eq!(["a", ""].into_address("x"), Err(AddressErr::Empty));
+        assert_eq!([""].into_address(""), Err(AddressErr::Empty));
+        assert_eq!(
+            ["/"].into_address("x"),
+            Err(AddressErr::InvalidCharacter(b'/'))
+        );
+        assert_eq!(
+            [""; 0].into_address("/"),
+            Err(AddressErr::InvalidCharacter(b'/'))
+        );
+        assert_eq!(
+            [""; 0].into_address(" "),
+            Err(AddressErr::InvalidCharacter(b' '))
+        );
+        // Segments are checked in order, so the first problem wins.
+        assert_eq!(["", "?"].into_address("x"), Err(AddressErr::Empty));
+        assert_eq!(["a"].into_address("x").map(|a| a.encoded_len()), Ok(8));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn slice_matches_array() {
+        let array = ["a", "bc", "def"];
+        let from_slice = (&array[..]).into_osc([], "xyz").unwrap();
+        let from_array = array.into_osc([], "xyz").unwrap();
+        assert!(from_slice.into_iter().eq(from_array));
+        let empty: &[f32] = &[];
+        assert!(empty
+            .into_osc([], "xyz")
+            .unwrap()
+            .into_iter()
+            .eq(b"/xyz\0\0\0\0,\0\0\0".iter().copied()));
+        assert_eq!(
+            (&["caf\u{e9}"][..]).into_osc([], "xyz"),
+            Err(AddressErr::StringErr(InvalidContents::NonAscii {
+                index: 3
+            }))
+        );
+    }
+
+    #[test]
+    fn twelve_element_tuple() {
+        let msg = (1, 2.0, 3, 4.0, 5, 6.0, 7, 8.0, 9, 10.0, 11, 12.0)
+            .into_osc([], "x")
+            .unwrap();
+        let bytes: Vec<u8> = msg.into_iter().collect();
+        // Address, then a comma, 12 tags, and a null terminator padded to 16, then 12 4-byte values.
+        assert_eq!(bytes.len(), 4 + 16 + 12 * 4);
+        assert_eq!(bytes.get(4..20), Some(&b",ifififififif\0\0\0"[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn address_accessors() {
+        let address = Address::decode(&mut b"/a/b/c\0\0".iter().copied()).unwrap();
+        assert_eq!(address.segments(), ["a", "b"]);
+        assert_eq!(address.method(), "c");
+        assert_eq!(address.to_string(), "/a/b/c");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn message_accessors() {
+        let bytes: Vec<u8> = (1000, -1, "hello")
+            .into_osc(["synth"], "foo")
+            .unwrap()
+            .into_iter()
+            .collect();
+        let message = Message::decode(&mut bytes.iter().copied()).unwrap();
+        assert_eq!(message.address().method(), "foo");
+        assert_eq!(message.data().len(), 3);
+        let (address, data) = message.into_parts();
+        assert_eq!(address.segments(), ["synth"]);
+        assert_eq!(data.get_str(2), Some("hello"));
+    }
+
+    /// Address segment that counts how many times its characters have been read.
+    #[derive(Clone, Copy, Debug)]
+    struct Counted(&'static str);
+
+    /// Number of times any `Counted` has been read.
+    static READS: AtomicUsize = AtomicUsize::new(0);
+
+    impl IntoIntoAddress for Counted {
+        type IntoAddr = core::str::Bytes<'static>;
+        fn into_into_addr(self) -> Self::IntoAddr {
+            let _ = READS.fetch_add(1, Ordering::Relaxed);
+            self.0.bytes()
+        }
+    }
+
+    #[test]
+    fn cached_address_skips_validation() {
+        let address = Address::from_validated([Counted("synth")], Counted("gain"));
+        address.validate().unwrap();
+        let before = READS.load(Ordering::Relaxed);
+        for _ in 0..1000 {
+            let message = Message::new(address.clone(), (1.0.into_atomic().unwrap(),));
+            assert_eq!(message.into_iter().count(), 20);
+        }
+        // Encoding reads each of the two segments once.
+        assert_eq!(READS.load(Ordering::Relaxed) - before, 2 * 1000);
+        let uncached = READS.load(Ordering::Relaxed);
+        for _ in 0..1000 {
+            let message = 1.0.into_osc([Counted("synth")], Counted("gain")).unwrap();
+            assert_eq!(message.into_iter().count(), 20);
+        }
+        // Validating on every call reads each segment twice.
+        assert_eq!(READS.load(Ordering::Relaxed) - uncached, 4 * 1000);
+        assert_eq!(
+            Address::from_validated([Counted("a b")], Counted("c")).validate(),
+            Err(AddressErr::InvalidCharacter(b' '))
+        );
+    }
+
+    /// Single integer argument that counts every byte read out of it.
+    #[derive(Clone, Copy, Debug)]
+    struct Watched(i32);
+
+    /// Number of bytes read out of any `Watched`.
+    static WATCHED: AtomicUsize = AtomicUsize::new(0);
+
+    impl crate::Tuple for Watched {
+        fn tag_at(&self, index: usize) -> Option<u8> {
+            (index == 0).then_some(b'i')
+        }
+        type Chained = core::iter::Inspect<core::array::IntoIter<u8, 4>, fn(&u8)>;
+        fn chain(self) -> Self::Chained {
+            let watch: fn(&u8) = |_byte| {
+                let _reads = WATCHED.fetch_add(1, Ordering::Relaxed);
+            };
+            self.0.to_be_bytes().into_iter().inspect(watch)
+        }
+        fn byte_len(&self) -> Option<usize> {
+            Some(4)
+        }
+    }
+
+    /// Single integer argument that doesn't say how long it is, like a `Tuple` written outside this crate.
+    #[derive(Clone, Copy, Debug)]
+    struct Plain(i32);
+
+    impl crate::Tuple for Plain {
+        fn tag_at(&self, index: usize) -> Option<u8> {
+            (index == 0).then_some(b'i')
+        }
+        type Chained = core::array::IntoIter<u8, 4>;
+        fn chain(self) -> Self::Chained {
+            self.0.to_be_bytes().into_iter()
+        }
+    }
+
+    #[test]
+    fn tuple_without_byte_len() {
+        let message = Message::new(["synth"].into_address("gain").unwrap(), Plain(7));
+        let expected = Message::new(
+            ["synth"].into_address("gain").unwrap(),
+            (7.into_atomic().unwrap(),),
+        );
+        assert_eq!(message.encoded_len(), expected.encoded_len());
+        assert_eq!(message.clone().into_iter().size_hint().1, None);
+        assert_eq!(message.clone().into_iter().count(), 20);
+        assert!(message.into_iter().eq(expected));
+    }
+
+    #[test]
+    fn message_count_without_iterating() {
+        let message = Message::new(["synth"].into_address("gain").unwrap(), Watched(7));
+        let before = WATCHED.load(Ordering::Relaxed);
+        let manual = message.clone().into_iter().fold(0_usize, |n, _byte| n + 1);
+        assert_eq!(manual, 20);
+        assert_eq!(WATCHED.load(Ordering::Relaxed) - before, 4);
+        let untouched = WATCHED.load(Ordering::Relaxed);
+        assert_eq!(message.clone().into_iter().count(), manual);
+        let mut partial = message.clone().into_iter();
+        assert_eq!(partial.nth(5), Some(b'h'));
+        assert_eq!(partial.count(), manual - 6);
+        let mut past_address = message.into_iter();
+        assert_eq!(past_address.nth(12), Some(b','));
+        assert_eq!(past_address.size_hint(), (7, Some(7)));
+        assert_eq!(past_address.count(), 7);
+        assert_eq!(WATCHED.load(Ordering::Relaxed), untouched);
+    }
+
+    #[test]
+    fn message_last_skips_address() {
+        let message = Message::new(["synth"].into_address("gain").unwrap(), Watched(7));
+        assert_eq!(message.clone().into_iter().last(), Some(7));
+        let mut past_tags = message.clone().into_iter();
+        assert_eq!(past_tags.nth(16), Some(0));
+        assert_eq!(past_tags.last(), Some(7));
+        let mut drained = message.into_iter();
+        assert_eq!(drained.nth(19), Some(7));
+        assert_eq!(drained.last(), None);
+        let no_args = Message::new([].into_address("ping").unwrap(), ());
+        assert_eq!(no_args.clone().into_iter().last(), Some(0));
+        let mut tags_only = no_args.clone().into_iter();
+        assert_eq!(tags_only.nth(8), Some(b','));
+        assert_eq!(tags_only.last(), Some(0));
+        let mut no_args_drained = no_args.into_iter();
+        assert_eq!(no_args_drained.nth(11), Some(0));
+        assert_eq!(no_args_drained.last(), None);
+    }
+
+    #[test]
+    fn address_display() {
+        let address = ["lighting", "right"].into_address("brightness").unwrap();
+        assert_eq!(address.to_string(), "/lighting/right/brightness");
+        assert_eq!([].into_address("foo").unwrap().to_string(), "/foo");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn dynamic_accessors() {
+        let bytes = b"\
+            ,ifs\0\0\0\0\
+            \x00\x00\x03\xE8\
+            \x3F\xC0\x00\x00\
+            hi\0\0";
+        let dynamic = Dynamic::decode(&mut bytes.iter().copied()).unwrap();
+        assert_eq!(dynamic.len(), 3);
+        assert!(!dynamic.is_empty());
+        assert_eq!(dynamic.get_int(0), Some(1000));
+        assert_eq!(
+            dynamic.get_float(1).map(f32::to_bits),
+            Some(1.5_f32.to_bits())
+        );
+        assert_eq!(dynamic.get_str(2), Some("hi"));
+        assert_eq!(dynamic.get_blob(2), None);
+        assert_eq!(dynamic.get_float(0), None);
+        assert_eq!(dynamic.get_int(1), None);
+        assert_eq!(dynamic.get_int(3), None);
+        assert!(Dynamic::default().is_empty());
+    }
+
+    #[test]
+    fn encode_bytes_by_reference() {
+        let message = (1000_i32, "hello").into_osc(["a"], "b").unwrap();
+        assert!(message.encode_bytes().eq(message.encode_bytes()));
+        assert!(message.encode_bytes().eq(message.clone()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn encode_dynamic_bytes_by_reference() {
+        let message = Message::new(
+            Address::parse("/left/gain").unwrap(),
+            Dynamic(vec![1000.into(), 1.234.into()]),
+        );
+        let first: Vec<u8> = message.encode_bytes().collect();
+        let second: Vec<u8> = message.encode_bytes().collect();
+        assert_eq!(first, second);
+        assert_eq!(first, message.canonical_bytes());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn borrowed_string_into_osc() {
+        let owned = "hello".to_owned();
+        let message = (&owned).into_osc([], "greet").unwrap();
+        assert!(message
+            .into_iter()
+            .eq("hello".into_osc([], "greet").unwrap()));
+        assert_eq!(owned.len(), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn decode_skipping_unknown_tags() {
+        let bytes = b",iqf\0\0\0\0\
+            \x00\x00\x03\xE8\
+            \x01\x02\x03\x04\x05\x06\x07\x08\
+            \x3F\x80\x00\x00";
+        let size = |c| (c == b'q').then_some(8);
+        let decoded = Dynamic::decode_skipping_unknown(&mut bytes.iter().copied(), size).unwrap();
+        let unknown = Data::Unknown(
+            UnknownArg::new(b'q', b"\x01\x02\x03\x04\x05\x06\x07\x08".to_vec()).unwrap(),
+        );
+        assert_eq!(
+            decoded,
+            Dynamic(vec![1000.into(), unknown.clone(), 1.0.into()])
+        );
+        // Forwarded exactly as it came in.
+        assert_eq!(unknown.type_tag(), b'q');
+        let arguments = bytes.split_at(8).1;
+        assert!(crate::Tuple::chain(decoded.clone()).eq(arguments.iter().copied()));
+        assert_eq!(crate::Tuple::byte_len(&decoded), Some(arguments.len()));
+        assert!(crate::Tuple::type_tag(&decoded).eq(*b"iqf"));
+        assert_eq!(
+            Dynamic::decode_skipping_unknown(&mut bytes.iter().copied(), |_| None),
+            Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(
+                TagDecodeErr::UnrecognizedTypeTag(b'q')
+            )))
+        );
+        assert_eq!(
+            Dynamic::decode_skipping_unknown(&mut bytes.iter().copied().take(16), size),
+            Err(Misaligned4B::End)
+        );
+        assert_eq!(
+            Dynamic::decode_skipping_unknown(&mut bytes.iter().copied(), |_| Some(6)),
+            Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(
+                TagDecodeErr::UnrecognizedTypeTag(b'q')
+            )))
+        );
+        // Only arguments that encode to a well-formed message can be built by hand.
+        assert_eq!(
+            UnknownArg::new(b'i', vec![0; 4]),
+            Err(UnknownArgErr::RecognizedTag(Tag::Integer))
+        );
+        assert_eq!(UnknownArg::new(0, vec![0; 4]), Err(UnknownArgErr::NullTag));
+        assert_eq!(
+            UnknownArg::new(b'q', vec![1, 2, 3]),
+            Err(UnknownArgErr::Misaligned)
+        );
+        let empty = UnknownArg::new(b'q', vec![]).unwrap();
+        assert_eq!((empty.tag(), empty.bytes()), (b'q', &[][..]));
+        // Same tag limit as `Tags::decode`.
+        let mut many = vec![b','];
+        many.resize(1 << 17, b'q');
+        many.extend([0, 0, 0, 0]);
+        assert_eq!(
+            Dynamic::decode_skipping_unknown(&mut many.into_iter(), size),
+            Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(
+                TagDecodeErr::TooMany
+            )))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn message_into_flat() {
+        let bytes = b"/a/b/c\0\0,if\0\0\0\x03\xE8\x3F\x80\x00\x00";
+        let (address, data) = Message::decode(&mut bytes.iter().copied())
+            .unwrap()
+            .into_flat();
+        assert_eq!(address, "/a/b/c");
+        assert_eq!(data, Dynamic(vec![1000.into(), 1.0.into()]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn borrowed_and_owned_strings_hash_alike() {
+        use core::hash::BuildHasher as _;
+        let owned = DynamicString::try_from("gain".to_owned()).unwrap();
+        let borrowed = crate::String::try_from("gain").unwrap();
+        assert_eq!(owned, borrowed);
+        assert_eq!(borrowed, owned);
+        let state = std::hash::RandomState::new();
+        assert_eq!(state.hash_one(&owned), state.hash_one(borrowed));
+        let mut dispatch = std::collections::HashMap::new();
+        let _: Option<i32> = dispatch.insert(owned, 1_i32);
+        assert_eq!(dispatch.get(<&str>::from(borrowed)), Some(&1));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn decode_per_osc_version() {
+        let v1_0 = DecodeConfig::new(OscVersion::V1_0);
+        let v1_1 = DecodeConfig::new(OscVersion::V1_1);
+        // `T` isn't supported at all yet, so neither version accepts it.
+        for config in [v1_0, v1_1] {
+            assert_eq!(
+                Dynamic::decode_with_config(&mut b",T\0\0".iter().copied(), config),
+                Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(
+                    TagDecodeErr::UnrecognizedTypeTag(b'T')
+                )))
+            );
+            assert_eq!(
+                Dynamic::decode_with_config(&mut b",i\0\0\0\0\0\x01".iter().copied(), config),
+                Ok(Dynamic(vec![1.into()]))
+            );
+        }
+        #[cfg(feature = "nonstandard-types")]
+        {
+            assert_eq!(
+                Dynamic::decode_with_config(&mut b",N\0\0".iter().copied(), v1_0),
+                Err(Misaligned4B::Other(DynamicDecodeErr::TypeTagErr(
+                    TagDecodeErr::NotInVersion(b'N')
+                )))
+            );
+            assert_eq!(
+                Dynamic::decode_with_config(&mut b",N\0\0".iter().copied(), v1_1),
+                Ok(Dynamic(vec![Data::Nil]))
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn extract_typed_tuple() {
+        let decoded = Dynamic::decode_slice(b",if\0\0\0\0\x05\x3F\x80\0\0").unwrap();
+        assert_eq!(decoded.clone().extract::<(i32, f32)>(), Ok((5, 1.0)));
+        assert_eq!(
+            decoded.clone().extract::<(i32, i32)>(),
+            Err(SchemaMismatch::Type {
+                index: 1,
+                actual: b'f'
+            })
+        );
+        assert_eq!(
+            decoded.extract::<(i32,)>(),
+            Err(SchemaMismatch::Count {
+                expected: 1,
+                actual: 2
+            })
+        );
+        let strings = Dynamic(vec![Data::String(
+            DynamicString::try_from("hi".to_owned()).unwrap(),
+        )]);
+        assert_eq!(strings.extract::<(String,)>(), Ok(("hi".to_owned(),)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn large_dynamic_type_tag() {
+        let data: Dynamic = (0_u8..100)
+            .map(|i| {
+                if i.is_multiple_of(2) {
+                    Data::from(i32::from(i))
+                } else {
+                    Data::from(0.5_f32)
+                }
+            })
+            .collect();
+        let expected: Vec<Tag> = (0_u8..100)
+            .map(|i| {
+                if i.is_multiple_of(2) {
+                    Tag::Integer
+                } else {
+                    Tag::Float
+                }
+            })
+            .collect();
+        assert!(crate::Tuple::type_tag(&data).eq(expected.iter().copied()));
+        assert!(crate::Tuple::type_tag(&&data).eq(expected.iter().copied()));
+        // Read straight out of the arguments, one at a time.
+        let mut lazy = crate::Tuple::type_tag(&data);
+        assert_eq!(lazy.nth(99), Some(b'f'));
+        assert_eq!(lazy.next(), None);
+        let message = Message::new(Address::parse("/big").unwrap(), data);
+        let owned: Vec<u8> = message.clone().into_iter().collect();
+        let borrowed: Vec<u8> = message.encode_bytes().collect();
+        assert_eq!(owned, borrowed);
+        // Address, then a comma, 100 tags, and a null terminator padded to 104 bytes.
+        assert_eq!(owned.get(8..9), Some(b",".as_slice()));
+        assert_eq!(owned.get(109..112), Some(b"\0\0\0".as_slice()));
+    }
+
+    #[test]
+    fn little_endian_debug_mode() {
+        let bytes = [0x00, 0x00, 0x01, 0x02];
+        let little = DecodeConfig::default().with_endianness(Endian::Little);
+        assert_eq!(
+            Integer::decode_with_config(&mut bytes.into_iter(), DecodeConfig::default())
+                .map(i32::from),
+            Ok(0x0102)
+        );
+        assert_eq!(
+            Integer::decode_with_config(&mut bytes.into_iter(), little).map(i32::from),
+            Ok(0x0201_0000)
+        );
+        assert_eq!(
+            Float::decode_with_config(&mut [0, 0, 0x80, 0x3F].into_iter(), little).map(f32::from),
+            Ok(1.0)
+        );
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            Dynamic::decode_with_config(&mut b",i\0\0\x05\0\0\0".iter().copied(), little),
+            Ok(Dynamic(vec![5.into()]))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn dynamic_from_iterator() {
+        let mut dynamic: Dynamic = [Data::Integer(1000.into()), Data::Float(1.234.into())]
+            .into_iter()
+            .collect();
+        assert_eq!(dynamic.len(), 2);
+        assert!(dynamic
+            .clone()
+            .into_osc([], "foo")
+            .unwrap()
+            .into_iter()
+            .eq((1000_i32, 1.234_f32).into_osc([], "foo").unwrap()));
+        dynamic.extend([Data::from(-1_i32)]);
+        assert_eq!(dynamic.len(), 3);
+        assert_eq!(dynamic.get_int(2), Some(-1));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn dynamic_push() {
+        let mut dynamic = Dynamic::default();
+        dynamic.push_int(1000);
+        dynamic.push_float(-1.0);
+        dynamic.push_blob(b"\x01\x02").unwrap();
+        dynamic.push_str("hello").unwrap();
+        dynamic.push(Data::from(7_i32));
+        assert_eq!(
+            dynamic.push_str("caf\u{e9}"),
+            Err(InvalidContents::NonAscii { index: 3 })
+        );
+        assert!(dynamic.tags().eq([
+            Tag::Integer,
+            Tag::Float,
+            Tag::Blob,
+            Tag::String,
+            Tag::Integer
+        ]));
+        assert_eq!(dynamic.get_blob(2), Some(&b"\x01\x02"[..]));
+        assert_eq!(dynamic.remove(5), None);
+        assert_eq!(dynamic.remove(4), Some(Data::from(7_i32)));
+        assert!(matches!(dynamic.remove(2), Some(Data::Blob(_))));
+        assert!(dynamic.into_osc(["a"], "push").unwrap().into_iter().eq((
+            1000_i32, -1.0_f32, "hello"
+        )
+            .into_osc(["a"], "push")
+            .unwrap()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn dynamic_iteration() {
+        let payload = b",ifs\0\0\0\0\x00\x00\x03\xE8\x3F\x9D\xF3\xB6hi\0\0";
+        let dynamic = Dynamic::decode(&mut payload.iter().copied()).unwrap();
+        assert!(dynamic.tags().eq([Tag::Integer, Tag::Float, Tag::String]));
+        let mut strings = 0_usize;
+        for data in &dynamic {
+            if matches!(data, &Data::String(ref s) if s == "hi") {
+                strings = strings.saturating_add(1);
+            }
+        }
+        assert_eq!(strings, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn data_from_rust_types() {
+        let dynamic = Dynamic(vec![
+            1000_i32.into(),
+            1.234_f32.into(),
+            DynamicString::try_from("hello".to_owned()).unwrap().into(),
+        ]);
+        assert!(dynamic.into_osc([], "foo").unwrap().into_iter().eq(b"\
+            /foo\0\0\0\0\
+            ,ifs\0\0\0\0\
+            \x00\x00\x03\xE8\
+            \x3F\x9D\xF3\xB6\
+            hello\0\0\0"
+            .iter()
+            .copied()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn borrowed_data_encodes_twice() {
+        let data = vec![
+            Data::from(1000),
+            Data::from(1.234),
+            Data::String("hello".to_owned().into_atomic().unwrap()),
+            Data::Blob(vec![1, 2, 3].into_atomic().unwrap()),
+        ];
+        let first: Vec<u8> = Message::new(["a"].into_address("b").unwrap(), data.as_slice())
+            .into_iter()
+            .collect();
+        let second: Vec<u8> = Message::new(["a"].into_address("b").unwrap(), data.as_slice())
+            .into_iter()
+            .collect();
+        assert_eq!(first, second);
+        assert!(Message::new(["a"].into_address("b").unwrap(), data)
+            .into_iter()
+            .eq(first));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn encoded_eq_across_representations() {
+        let typed = (1000, 1.234).into_osc(["synth"], "params").unwrap();
+        let dynamic = Message::new(
+            vec!["synth".to_owned()]
+                .into_address("params".to_owned())
+                .unwrap(),
+            Dynamic(vec![1000.into(), 1.234.into()]),
+        );
+        assert!(typed.encoded_eq(&dynamic));
+        assert!(dynamic.encoded_eq(&typed));
+        assert_eq!(
+            typed.canonical_bytes(),
+            dynamic.into_iter().collect::<Vec<u8>>()
+        );
+        assert!(!typed.encoded_eq(&(1000, 1.234).into_osc(["synth"], "other").unwrap()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn hexdump_message_foo() {
+        let osc = (1000, -1, "hello", 1.234, 5.678)
+            .into_osc([], "foo")
+            .unwrap();
+        let dump = osc.hexdump();
+        let mut lines = dump.lines();
+        assert_eq!(
+            lines.next(),
+            Some("2f 66 6f 6f 00 00 00 00 2c 69 69 73 66 66 00 00 | /foo....,iisff..")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("00 00 03 e8 ff ff ff ff 68 65 6c 6c 6f 00 00 00 | ..??????hello...")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("3f 9d f3 b6 40 b5 b2 2d                         | ????@??-")
+        );
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn borrow_decoded_contents() {
+        let blob =
+            DynamicBlob::decode(&mut b"\x00\x00\x00\x03\x01\x02\x03\0".iter().copied()).unwrap();
+        assert_eq!(blob.as_slice(), &[1, 2, 3]);
+        assert_eq!(AsRef::<[u8]>::as_ref(&blob), &[1, 2, 3]);
+        let string = DynamicString::decode(&mut b"hello\0\0\0".iter().copied()).unwrap();
+        assert_eq!(string.as_str(), "hello");
+        assert_eq!(AsRef::<str>::as_ref(&string), "hello");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn data_vec_roundtrip() {
+        let data = vec![
+            Data::Integer(1000.into_atomic().unwrap()),
+            Data::Float(1.234.into_atomic().unwrap()),
+            Data::String("hello".to_owned().into_atomic().unwrap()),
+        ];
+        let address = ["a"].into_address("b").unwrap();
+        let encoded: Vec<u8> = Message::new(address, data.clone()).into_iter().collect();
+        let decoded = Message::decode(&mut encoded.iter().copied()).unwrap();
+        assert!(decoded.into_iter().eq(encoded.iter().copied()));
+        assert!(Dynamic(data)
+            .into_osc(["a"], "b")
+            .unwrap()
+            .into_iter()
+            .eq(encoded));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn builder_matches_tuple() {
+        let mut builder = MessageBuilder::new(vec!["synth"], "params").add_int(1000);
+        for f in [1.234, 5.678] {
+            builder = builder.add_float(f);
+        }
+        let built = builder
+            .add_string("hello")
+            .unwrap()
+            .add_blob(&[1, 2, 3])
+            .unwrap()
+            .build()
+            .unwrap();
+        let tuple = (1000, 1.234, 5.678, "hello", &[1_u8, 2, 3][..])
+            .into_osc(["synth"], "params")
+            .unwrap();
+        assert!(built.into_iter().eq(tuple));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn builder_invalid_string() {
+        assert_eq!(
+            MessageBuilder::new([], "foo").add_string("caf\u{e9}"),
+            Err(InvalidContents::NonAscii { index: 3 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn message_stream_back_to_back() {
+        let first: Vec<u8> = (440.)
+            .into_osc(["oscillator", "4"], "frequency")
+            .unwrap()
+            .into_iter()
+            .collect();
+        let second: Vec<u8> = (1000, -1, "hello")
+            .into_osc([], "foo")
+            .unwrap()
+            .into_iter()
+            .collect();
+        let mut stream = MessageStream::new(first.iter().chain(&second).copied());
+        assert!(stream
+            .next()
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .eq(first.iter().copied()));
+        assert!(stream
+            .next()
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .eq(second.iter().copied()));
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn incremental_one_byte_at_a_time() {
+        let encoded: Vec<u8> = (1000, "hello")
+            .into_osc(["synth"], "params")
+            .unwrap()
+            .into_iter()
+            .collect();
+        let mut decoder = IncrementalDecoder::<Message>::new();
+        let (last, init) = encoded.split_last().unwrap();
+        for &byte in init {
+            assert_eq!(decoder.push(&[byte]), Ok(None));
+        }
+        let message = decoder.push(&[*last]).unwrap().unwrap();
+        assert!(message.into_iter().eq(encoded.iter().copied()));
+        assert_eq!(decoder.buffered(), 0);
+        assert_eq!(
+            decoder.push(b"/foo\0\0\0\0x\0\0\0"),
+            Err(MessageDecodeErr::DataErr(DynamicDecodeErr::TypeTagErr(
+                TagDecodeErr::MissingComma(b'x')
+            )))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn message_stream_truncated() {
+        let bytes: Vec<u8> = (1000, -1, "hello")
+            .into_osc([], "foo")
+            .unwrap()
+            .into_iter()
+            .collect();
+        let mut stream = MessageStream::new(bytes.iter().chain(bytes.iter().take(12)).copied());
+        assert!(stream
+            .next()
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .eq(bytes.iter().copied()));
+        assert_eq!(stream.next().unwrap(), Err(Misaligned4B::End));
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn dynamic_truncated_integer() {
+        let truncated = b",i\0\0\x00\x01";
+        assert_eq!(
+            Dynamic::decode(&mut truncated.iter().copied()),
+            Err(Misaligned4B::Misaligned)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn dynamic_missing_integer() {
+        let truncated = b",i\0\0";
+        assert_eq!(
+            Dynamic::decode(&mut truncated.iter().copied()),
+            Err(Misaligned4B::End)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn decode_tuple_message_foo() {
+        let payload = b"\
+            \x00\x00\x03\xE8\
+            \xFF\xFF\xFF\xFF\
+            hello\0\0\0\
+            \x3F\x9D\xF3\xB6\
+            \x40\xB5\xB2\x2D";
+        let mut iter = payload.iter().copied();
+        let decoded = <(Integer, Integer, DynamicString, Float, Float)>::decode(&mut iter);
+        assert_eq!(
+            decoded,
+            Ok((
+                1000.into_atomic().unwrap(),
+                (-1).into_atomic().unwrap(),
+                "hello".to_owned().into_atomic().unwrap(),
+                1.234.into_atomic().unwrap(),
+                5.678.into_atomic().unwrap(),
+            ))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn decode_packet_message() {
+        let payload = b"/foo\0\0\0\0,i\0\0\x00\x00\x03\xE8";
+        let decoded = Packet::decode(&mut payload.iter().copied()).unwrap();
+        assert!(
+            matches!(decoded, Packet::Message(ref message) if message.address().method() == "foo")
+        );
+        assert!(decoded.into_iter().eq(payload.iter().copied()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn decode_packet_bundle() {
+        let payload = b"\
+            #bundle\0\
+            \x00\x00\x00\x00\x00\x00\x00\x01\
+            \x00\x00\x00\x10\
+            /foo\0\0\0\0,i\0\0\x00\x00\x03\xE8\
+            \x00\x00\x00\x10\
+            #bundle\0\x00\x00\x00\x00\x00\x00\x00\x01";
+        let decoded = Packet::decode(&mut payload.iter().copied()).unwrap();
+        assert!(matches!(
+            decoded,
+            Packet::Bundle(ref bundle) if bundle.time_tag() == TimeTag::immediately()
+                && matches!(bundle.elements(), &[Packet::Message(_), Packet::Bundle(_)])
+        ));
+        assert_eq!(decoded.encoded_len(), payload.len());
+        assert!(decoded.into_iter().eq(payload.iter().copied()));
+        assert_eq!(
+            Packet::decode(&mut b"\x00\x00\x00\x00".iter().copied()),
+            Err(Misaligned4B::Other(PacketDecodeErr::UnrecognizedStart(0)))
+        );
+        assert_eq!(DynamicBundle::default().encoded_len(), 16);
+        let oversized = b"\
+            #bundle\0\
+            \x00\x00\x00\x00\x00\x00\x00\x01\
+            \x00\x00\x00\x14\
+            /foo\0\0\0\0,i\0\0\x00\x00\x03\xE8";
+        assert_eq!(
+            Packet::decode(&mut oversized.iter().copied()),
+            Err(Misaligned4B::Other(PacketDecodeErr::BundleErr(
+                BundleDecodeErr::SizeMismatch
+            )))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn bundle_due() {
+        let first = Message::new(Address::parse("/a").unwrap(), Dynamic(vec![1.into()]));
+        let second = Message::new(Address::parse("/b").unwrap(), Dynamic(vec![2.into()]));
+        let third = Message::new(Address::parse("/c").unwrap(), Dynamic(vec![3.into()]));
+        let bundle = DynamicBundle::new(
+            TimeTag::new(100, 0),
+            vec![
+                first.clone().into(),
+                DynamicBundle::new(TimeTag::new(200, 0), vec![second.clone().into()])
+                    .unwrap()
+                    .into(),
+                DynamicBundle::new(TimeTag::immediately(), vec![third.clone().into()])
+                    .unwrap()
+                    .into(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(bundle.due(TimeTag::new(99, u32::MAX)).count(), 0);
+        assert!(bundle.due(TimeTag::new(100, 0)).eq([&first, &third]));
+        assert!(bundle
+            .due(TimeTag::new(200, 0))
+            .eq([&first, &second, &third]));
+        let now = DynamicBundle::new(TimeTag::immediately(), vec![first.clone().into()]).unwrap();
+        assert!(now.due(TimeTag::new(0, 0)).eq([&first]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn bundle_elements_unordered() {
+        let first = Message::new(Address::parse("/a").unwrap(), Dynamic(vec![1.into()]));
+        let second = Message::new(Address::parse("/b").unwrap(), Dynamic(vec![2.into()]));
+        let forward = DynamicBundle::new(
+            TimeTag::new(1, 0),
+            vec![first.clone().into(), second.clone().into()],
+        )
+        .unwrap();
+        let backward = DynamicBundle::new(
+            TimeTag::new(1, 0),
+            vec![second.clone().into(), first.clone().into()],
+        )
+        .unwrap();
+        assert_ne!(forward, backward);
+        assert!(forward.elements_eq_unordered(&backward));
+        let doubled = DynamicBundle::new(
+            TimeTag::new(1, 0),
+            vec![first.clone().into(), first.clone().into()],
+        )
+        .unwrap();
+        assert!(!forward.elements_eq_unordered(&doubled));
+        let fewer = DynamicBundle::new(TimeTag::new(1, 0), vec![second.clone().into()]).unwrap();
+        assert!(!forward.elements_eq_unordered(&fewer));
+        let nested = DynamicBundle::new(
+            TimeTag::new(1, 0),
+            vec![forward.clone().into(), second.clone().into()],
+        )
+        .unwrap();
+        let nested_backward = DynamicBundle::new(
+            TimeTag::new(1, 0),
+            vec![second.clone().into(), forward.clone().into()],
+        )
+        .unwrap();
+        assert!(nested.elements_eq_unordered(&nested_backward));
+        let nested_reordered = DynamicBundle::new(
+            TimeTag::new(1, 0),
+            vec![second.clone().into(), backward.clone().into()],
+        )
+        .unwrap();
+        assert!(!nested.elements_eq_unordered(&nested_reordered));
+        let packets: [Packet; 6] = [
+            first.into(),
+            second.into(),
+            forward.into(),
+            backward.into(),
+            fewer.into(),
+            nested.into(),
+        ];
+        for a in &packets {
+            for b in &packets {
+                assert_eq!(
+                    a.cmp_encoded(b),
+                    a.clone().into_iter().cmp(b.clone()),
+                    "{a:?} vs. {b:?}",
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn bundle_split_for_mtu() {
+        // Each of these encodes to 16 bytes, plus 4 for its size within a bundle.
+        let messages: Vec<Message> = (0_i32..5)
+            .map(|i| Message::new(Address::parse("/abc").unwrap(), Dynamic(vec![i.into()])))
+            .collect();
+        let bundle = DynamicBundle::new(
+            TimeTag::new(7, 0),
+            messages.iter().cloned().map(Into::into).collect(),
+        )
+        .unwrap();
+        // Room for two elements (16 + 2 * 20) but not three.
+        let split = bundle.clone().split_for_mtu(56);
+        assert_eq!(split.len(), 3);
+        assert!(split
+            .iter()
+            .all(|sub| sub.time_tag() == TimeTag::new(7, 0) && sub.encoded_len() <= 56));
+        assert!(split
+            .iter()
+            .flat_map(|sub| sub.due(TimeTag::new(7, 0)))
+            .eq(messages.iter()));
+        // Too small for any element: each still goes out alone.
+        let alone = bundle.clone().split_for_mtu(8);
+        assert_eq!(alone.len(), 5);
+        // Big enough for everything: unchanged.
+        assert_eq!(bundle.clone().split_for_mtu(usize::MAX), vec![bundle]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse_tags() {
+        assert_eq!(
+            Tags::parse(",ifs"),
+            Ok(Tags(vec![Tag::Integer, Tag::Float, Tag::String]))
+        );
+        assert_eq!(Tags::parse(","), Ok(Tags(vec![])));
+        assert_eq!(Tags::parse("ifs"), Err(TagDecodeErr::MissingComma(b'i')));
+        assert_eq!(Tags::parse(""), Err(TagDecodeErr::MissingComma(b'\0')));
+        assert_eq!(
+            Tags::parse(",ix"),
+            Err(TagDecodeErr::UnrecognizedTypeTag(b'x'))
+        );
+    }
 
     #[test]
     #[cfg(feature = "alloc")]
@@ -6,204 +6,361 @@
 
 //! Typed collection of data.
 
-use crate::{Atomic, InvalidContents, Tag};
+use crate::{Atomic, Decode, InvalidContents, Misaligned4B};
 use core::iter::Chain;
 
+#[cfg(feature = "alloc")]
+use crate::{BlobDecodeErr, StringDecodeErr};
+
 /// Typed collection of data.
 pub trait Tuple {
-    /// Iterator over characters in the formatted type tag.
-    type TypeTagIter: Iterator<Item = Tag>;
-    /// Format an OSC type tag for this collection of types.
-    fn type_tag(&self) -> Self::TypeTagIter;
+    /// Type tag of the argument at this position, or `None` past the end.
+    fn tag_at(&self, index: usize) -> Option<u8>;
+    /// Format an OSC type tag for this collection of types, reading each lazily out of `self`.
+    #[inline(always)]
+    fn type_tag(&self) -> TypeTags<'_, Self> {
+        TypeTags {
+            tuple: self,
+            index: 0,
+        }
+    }
     /// Chained iterators over each piece of data in this tuple.
     type Chained: Iterator<Item = u8>;
     /// Chain iterators over each piece of data in this tuple.
     fn chain(self) -> Self::Chained;
+    /// Exact number of bytes `chain` will produce, if known without producing them.
+    ///
+    /// `None` unless overridden, in which case `Message::encoded_len` encodes a copy to count its bytes
+    /// and a message iterator's `count` walks them. An override must return exactly `self.chain().count()`,
+    /// or encoded lengths will be wrong.
+    #[inline(always)]
+    fn byte_len(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Type tags of a `Tuple`, read one at a time without collecting them; see `Tuple::type_tag`.
+#[derive(Debug)]
+pub struct TypeTags<'t, T: Tuple + ?Sized> {
+    /// Collection whose types these are.
+    tuple: &'t T,
+    /// Position of the next tag.
+    index: usize,
+}
+
+impl<T: Tuple + ?Sized> Clone for TypeTags<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            tuple: self.tuple,
+            index: self.index,
+        }
+    }
 }
 
+impl<T: Tuple + ?Sized> Iterator for TypeTags<'_, T> {
+    type Item = u8;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let tag = self.tuple.tag_at(self.index)?;
+        self.index = self.index.saturating_add(1);
+        Some(tag)
+    }
+}
+
+impl<T: Tuple + ?Sized> core::iter::FusedIterator for TypeTags<'_, T> {}
+
 impl Tuple for () {
-    type TypeTagIter = core::iter::Empty<Tag>;
     #[inline(always)]
-    fn type_tag(&self) -> Self::TypeTagIter {
-        core::iter::empty()
+    fn tag_at(&self, _index: usize) -> Option<u8> {
+        None
     }
     type Chained = core::iter::Empty<u8>;
     #[inline(always)]
     fn chain(self) -> Self::Chained {
         core::iter::empty()
     }
+    #[inline(always)]
+    fn byte_len(&self) -> Option<usize> {
+        Some(0)
+    }
+}
+
+/// Left-nested `Chain` over each type's `IntoIter`, e.g. `Chain<Chain<A::IntoIter, B::IntoIter>, C::IntoIter>`.
+macro_rules! chained {
+    (@ $acc:ty;) => { $acc };
+    (@ $acc:ty; $head:ident $(, $tail:ident)*) => { chained!(@ Chain<$acc, $head::IntoIter>; $($tail),*) };
+    ($head:ident $(, $tail:ident)*) => { chained!(@ $head::IntoIter; $($tail),*) };
 }
 
 /// Implement `Tuple` for a tuple of types, each of which implement `Atomic`.
 macro_rules! impl_tuple {
-    ($n:expr, $chain:ty, |$self:ident| $chain_expr:expr, $($id:ident),+,) => {
-        impl<$($id: Atomic),+> Tuple for ($($id),+,)
+    ($n:expr, $head:ident $(, $tail:ident)*) => {
+        impl<$head: Atomic $(, $tail: Atomic)*> Tuple for ($head, $($tail,)*)
         where
-            $(InvalidContents: From<<$id as TryFrom<$id::AsRust>>::Error>),+,
+            InvalidContents: From<<$head as TryFrom<$head::AsRust>>::Error>,
+            $(InvalidContents: From<<$tail as TryFrom<$tail::AsRust>>::Error>,)*
         {
-            type TypeTagIter = core::array::IntoIter<Tag, $n>;
-            #[inline(always)]
-            fn type_tag(&self) -> Self::TypeTagIter {
+            #[inline]
+            fn tag_at(&self, index: usize) -> Option<u8> {
                 #[allow(non_snake_case)]
-                let &($(ref $id),+,) = self;
-                #[allow(clippy::as_conversions, trivial_casts)]
-                [$($id.type_tag()),+].into_iter()
+                let &(ref $head, $(ref $tail,)*) = self;
+                [$head.type_tag() $(, $tail.type_tag())*].get(index).copied()
             }
-            type Chained = $chain;
+            type Chained = chained!($head $(, $tail)*);
+            #[inline]
+            fn chain(self) -> Self::Chained {
+                #[allow(non_snake_case)]
+                let ($head, $($tail,)*) = self;
+                $head.into_iter()$(.chain($tail))*
+            }
+            #[inline]
+            fn byte_len(&self) -> Option<usize> {
+                #[allow(non_snake_case)]
+                let &(ref $head, $(ref $tail,)*) = self;
+                Some($head.byte_len()$(.saturating_add($tail.byte_len()))*)
+            }
+        }
+    };
+}
+
+impl_tuple!(1, A);
+impl_tuple!(2, A, B);
+impl_tuple!(3, A, B, C);
+impl_tuple!(4, A, B, C, D);
+impl_tuple!(5, A, B, C, D, E);
+impl_tuple!(6, A, B, C, D, E, F);
+impl_tuple!(7, A, B, C, D, E, F, G);
+impl_tuple!(8, A, B, C, D, E, F, G, H);
+impl_tuple!(9, A, B, C, D, E, F, G, H, I);
+impl_tuple!(10, A, B, C, D, E, F, G, H, I, J);
+impl_tuple!(11, A, B, C, D, E, F, G, H, I, J, K);
+impl_tuple!(12, A, B, C, D, E, F, G, H, I, J, K, L);
+impl_tuple!(13, A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_tuple!(14, A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_tuple!(15, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_tuple!(16, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+/// Any possible error while decoding a tuple of atomic OSC types.
+#[non_exhaustive]
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum TupleDecodeErr {
+    /// Error decoding a string.
+    #[cfg(feature = "alloc")]
+    StringErr(StringDecodeErr),
+    /// Error decoding a blob.
+    #[cfg(feature = "alloc")]
+    BlobErr(BlobDecodeErr),
+}
+
+impl core::fmt::Display for TupleDecodeErr {
+    #[inline]
+    #[cfg_attr(
+        not(feature = "alloc"),
+        allow(clippy::uninhabited_references, unused_variables)
+    )]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Dereferenced since, without `alloc`, this type is uninhabited.
+        match *self {
+            #[cfg(feature = "alloc")]
+            Self::StringErr(e) => write!(f, "{e}"),
+            #[cfg(feature = "alloc")]
+            Self::BlobErr(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<core::convert::Infallible> for TupleDecodeErr {
+    #[inline(always)]
+    fn from(value: core::convert::Infallible) -> Self {
+        match value {}
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<StringDecodeErr> for TupleDecodeErr {
+    #[inline(always)]
+    fn from(value: StringDecodeErr) -> Self {
+        Self::StringErr(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<BlobDecodeErr> for TupleDecodeErr {
+    #[inline(always)]
+    fn from(value: BlobDecodeErr) -> Self {
+        Self::BlobErr(value)
+    }
+}
+
+/// Implement `Decode` for a tuple of types, each of which implement `Atomic` and `Decode`.
+/// Note that this reads only the data, not the type tag, exactly mirroring `Tuple::chain`.
+macro_rules! impl_decode_tuple {
+    ($($id:ident),+) => {
+        impl<$($id: Atomic + Decode),+> Decode for ($($id),+,)
+        where
+            $(InvalidContents: From<<$id as TryFrom<$id::AsRust>>::Error>),+,
+            $(TupleDecodeErr: From<<$id as Decode>::Error>),+,
+        {
+            type Error = TupleDecodeErr;
             #[inline]
-            fn chain($self) -> Self::Chained {
-                $chain_expr
+            fn decode<Iter: Iterator<Item = u8>>(iter: &mut Iter) -> Result<Self, Misaligned4B<Self::Error>> {
+                Ok(($(<$id as Decode>::decode(iter).map_err(|e| e.map(TupleDecodeErr::from))?),+,))
             }
         }
     };
 }
 
-impl_tuple!(
-    1,
-    A::IntoIter,
-    |self| self.0.into_iter(),
-    A, //
-);
-impl_tuple!(
-    2,
-    Chain<A::IntoIter, B::IntoIter>,
-    |self| self.0.into_iter().chain(self.1),
-    A, B, //
-);
-impl_tuple!(
-    3,
-    Chain<Chain<A::IntoIter, B::IntoIter>, C::IntoIter>,
-    |self| self.0.into_iter().chain(self.1).chain(self.2),
-    A,
-    B,
-    C, //
-);
-impl_tuple!(
-    4,
-    Chain<Chain<Chain<A::IntoIter, B::IntoIter>, C::IntoIter>, D::IntoIter>,
-    |self| self.0.into_iter().chain(self.1).chain(self.2).chain(self.3),
-    A,
-    B,
-    C,
-    D, //
-);
-impl_tuple!(
-    5,
-    Chain<Chain<Chain<Chain<A::IntoIter, B::IntoIter>, C::IntoIter>, D::IntoIter>, E::IntoIter>,
-    |self| self
-        .0
-        .into_iter()
-        .chain(self.1)
-        .chain(self.2)
-        .chain(self.3)
-        .chain(self.4),
-    A,
-    B,
-    C,
-    D,
-    E, //
-);
-impl_tuple!(
-    6,
-    Chain<
-        Chain<Chain<Chain<Chain<A::IntoIter, B::IntoIter>, C::IntoIter>, D::IntoIter>, E::IntoIter>,
-        F::IntoIter,
-    >,
-    |self| self
-        .0
-        .into_iter()
-        .chain(self.1)
-        .chain(self.2)
-        .chain(self.3)
-        .chain(self.4)
-        .chain(self.5),
-    A,
-    B,
-    C,
-    D,
-    E,
-    F, //
-);
-impl_tuple!(
-    7,
-    Chain<
-        Chain<
-            Chain<
-                Chain<Chain<Chain<A::IntoIter, B::IntoIter>, C::IntoIter>, D::IntoIter>,
-                E::IntoIter,
-            >,
-            F::IntoIter,
-        >,
-        G::IntoIter,
-    >,
-    |self| self
-        .0
-        .into_iter()
-        .chain(self.1)
-        .chain(self.2)
-        .chain(self.3)
-        .chain(self.4)
-        .chain(self.5)
-        .chain(self.6),
-    A,
-    B,
-    C,
-    D,
-    E,
-    F,
-    G, //
-);
-impl_tuple!(
-    8,
-    Chain<
-        Chain<
-            Chain<
-                Chain<
-                    Chain<Chain<Chain<A::IntoIter, B::IntoIter>, C::IntoIter>, D::IntoIter>,
-                    E::IntoIter,
-                >,
-                F::IntoIter,
-            >,
-            G::IntoIter,
-        >,
-        H::IntoIter,
-    >,
-    |self| self
-        .0
-        .into_iter()
-        .chain(self.1)
-        .chain(self.2)
-        .chain(self.3)
-        .chain(self.4)
-        .chain(self.5)
-        .chain(self.6)
-        .chain(self.7),
-    A,
-    B,
-    C,
-    D,
-    E,
-    F,
-    G,
-    H, //
-);
+impl_decode_tuple!(A);
+impl_decode_tuple!(A, B);
+impl_decode_tuple!(A, B, C);
+impl_decode_tuple!(A, B, C, D);
+impl_decode_tuple!(A, B, C, D, E);
+impl_decode_tuple!(A, B, C, D, E, F);
+impl_decode_tuple!(A, B, C, D, E, F, G);
+impl_decode_tuple!(A, B, C, D, E, F, G, H);
+impl_decode_tuple!(A, B, C, D, E, F, G, H, I);
+impl_decode_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_decode_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_decode_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_decode_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_decode_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_decode_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_decode_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+impl<A: Atomic, const N: usize> Tuple for [A; N]
+where
+    InvalidContents: From<<A as TryFrom<A::AsRust>>::Error>,
+{
+    #[inline]
+    fn tag_at(&self, index: usize) -> Option<u8> {
+        self.get(index).map(A::type_tag)
+    }
+    type Chained = core::iter::Flatten<core::array::IntoIter<A, N>>;
+    #[inline]
+    fn chain(self) -> Self::Chained {
+        self.into_iter().flatten()
+    }
+    #[inline]
+    fn byte_len(&self) -> Option<usize> {
+        Some(self.iter().map(A::byte_len).fold(0, usize::saturating_add))
+    }
+}
+
+#[cfg(feature = "nonstandard-types")]
+impl<A: Atomic> Tuple for Option<A>
+where
+    InvalidContents: From<<A as TryFrom<A::AsRust>>::Error>,
+{
+    #[inline]
+    fn tag_at(&self, index: usize) -> Option<u8> {
+        (index == 0).then(|| self.as_ref().map_or(crate::Tag::Nil.as_byte(), A::type_tag))
+    }
+    type Chained = core::iter::Flatten<core::option::IntoIter<A>>;
+    #[inline]
+    fn chain(self) -> Self::Chained {
+        self.into_iter().flatten()
+    }
+    #[inline]
+    fn byte_len(&self) -> Option<usize> {
+        Some(self.as_ref().map_or(0, A::byte_len))
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl<A: Atomic> Tuple for alloc::vec::Vec<A>
+where
+    InvalidContents: From<<A as TryFrom<A::AsRust>>::Error>,
+{
+    type Chained = core::iter::Flatten<alloc::vec::IntoIter<A>>;
+    #[inline]
+    fn tag_at(&self, index: usize) -> Option<u8> {
+        self.get(index).map(A::type_tag)
+    }
+    #[inline]
+    fn chain(self) -> Self::Chained {
+        self.into_iter().flatten()
+    }
+    #[inline]
+    fn byte_len(&self) -> Option<usize> {
+        Some(self.iter().map(A::byte_len).fold(0, usize::saturating_add))
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[allow(unused_qualifications)]
+impl<'d> Tuple for &'d [crate::Data] {
+    type Chained = core::iter::Flatten<core::slice::Iter<'d, crate::Data>>;
+    #[inline]
+    fn tag_at(&self, index: usize) -> Option<u8> {
+        self.get(index).map(Atomic::type_tag)
+    }
+    /// Borrow each argument instead of moving it, so the same data can be encoded again.
+    #[inline]
+    fn chain(self) -> Self::Chained {
+        self.iter().flatten()
+    }
+    #[inline]
+    fn byte_len(&self) -> Option<usize> {
+        Some(
+            self.iter()
+                .map(Atomic::byte_len)
+                .fold(0, usize::saturating_add),
+        )
+    }
+}
 
 #[cfg(feature = "alloc")]
 #[allow(unused_qualifications)]
 impl Tuple for crate::Dynamic {
-    type TypeTagIter = alloc::vec::IntoIter<Tag>;
-    type Chained = core::iter::Flatten<alloc::vec::IntoIter<crate::Data>>;
+    type Chained = <alloc::vec::Vec<crate::Data> as Tuple>::Chained;
+    #[inline]
+    fn tag_at(&self, index: usize) -> Option<u8> {
+        self.0.tag_at(index)
+    }
+    #[inline]
+    fn chain(self) -> Self::Chained {
+        self.0.chain()
+    }
+    #[inline]
+    fn byte_len(&self) -> Option<usize> {
+        self.0.byte_len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'d> Tuple for &'d crate::Dynamic {
+    type Chained = <&'d [crate::Data] as Tuple>::Chained;
     #[inline]
-    fn type_tag(&self) -> Self::TypeTagIter {
-        self.0
-            .iter()
-            .map(crate::Data::type_tag)
-            .collect::<alloc::vec::Vec<_>>()
-            .into_iter()
+    fn tag_at(&self, index: usize) -> Option<u8> {
+        self.0.tag_at(index)
     }
+    /// Borrow each argument instead of moving it, so the same data can be encoded again.
     #[inline]
-    #[allow(clippy::as_underscore, trivial_casts)]
     fn chain(self) -> Self::Chained {
-        self.0.into_iter().flatten()
+        self.0.as_slice().chain()
+    }
+    #[inline]
+    fn byte_len(&self) -> Option<usize> {
+        self.0.as_slice().byte_len()
+    }
+}
+
+/// Borrowed tuples of `Copy` data (e.g. `(Integer, String<'_>)`) are encoded from a copy.
+impl<T: Tuple + Copy> Tuple for &T {
+    type Chained = T::Chained;
+    #[inline(always)]
+    fn tag_at(&self, index: usize) -> Option<u8> {
+        (**self).tag_at(index)
+    }
+    #[inline(always)]
+    fn chain(self) -> Self::Chained {
+        (*self).chain()
+    }
+    #[inline(always)]
+    fn byte_len(&self) -> Option<usize> {
+        (**self).byte_len()
     }
 }